@@ -1,7 +1,7 @@
 use std::fs::File;
 use std::io::Read;
 
-use intcode::Computer;
+use intcode::{Computer, Stop, SymbolicComputer};
 
 fn main() {
     let mut input = String::new();
@@ -22,19 +22,41 @@ fn main() {
 
     println!("Output: {}", c.get(0).unwrap());
 
-    for i in 0..=99 {
-        for j in 0..=99 {
-            let mut c = computer.clone();
+    let mut s = SymbolicComputer::new(&computer.memory().to_vec());
 
-            c.set(1, i);
-            c.set(2, j);
+    s.symbol(1, "noun");
+    s.symbol(2, "verb");
 
-            c.compute();
+    match s.run(100_000) {
+        Stop::Halted => {}
+        stop => panic!("symbolic execution stopped early: {:?}", stop),
+    }
+
+    let output = s.get(0).polynomial().expect("output is not a polynomial");
+
+    assert!(output.degree() <= 1, "output is not linear: {}", output);
+
+    let slope = output.coefficient(&["verb"]);
+
+    for noun in 0..=99 {
+        let rest = 19_690_720 - output.eval(|x| if x == "noun" { noun } else { 0 });
 
-            if c.get(0).unwrap() == 19_690_720 {
-                println!("Noun: {}, verb: {}, solution: {}", i, j, 100 * i + j);
-                return;
-            }
+        if slope == 0 || rest % slope != 0 {
+            continue;
+        }
+
+        let verb = rest / slope;
+
+        if (0..=99).contains(&verb) {
+            println!(
+                "Noun: {}, verb: {}, solution: {}",
+                noun,
+                verb,
+                100 * noun + verb
+            );
+            return;
         }
     }
+
+    panic!("no noun and verb produce 19690720 from {}", output);
 }
@@ -31,11 +31,11 @@ impl Line {
 
     fn distance_to(&self, point: &(i64, i64)) -> Option<usize> {
         if self.start.0 == point.0 && is_between(self.start.1, self.end.1, point.1) {
-            return Some((point.1 - self.start.1).abs() as usize);
+            return Some((point.1 - self.start.1).unsigned_abs() as usize);
         }
 
         if self.start.1 == point.1 && is_between(self.start.0, self.end.0, point.0) {
-            return Some((point.0 - self.start.0).abs() as usize);
+            return Some((point.0 - self.start.0).unsigned_abs() as usize);
         }
 
         None
@@ -85,7 +85,7 @@ fn main() {
 
     for line1 in &l1 {
         for line2 in &l2 {
-            if let Some(x) = line1.crosses(&line2) {
+            if let Some(x) = line1.crosses(line2) {
                 intersections.insert(x);
             }
         }
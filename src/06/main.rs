@@ -16,15 +16,12 @@ fn main() {
     input.lines().for_each(|l| {
         let split = l.split(')').collect::<Vec<&str>>();
 
-        orbits
-            .entry(split[0])
-            .or_insert_with(HashSet::new)
-            .insert(split[1]);
+        orbits.entry(split[0]).or_default().insert(split[1]);
     });
 
     let orbit_closure = orbits
         .keys()
-        .map(|k| (*k, get_closure(&orbits, *k)))
+        .map(|k| (*k, get_closure(&orbits, k)))
         .collect::<HashMap<&str, HashSet<&str>>>();
 
     println!(
@@ -3,10 +3,32 @@ use std::io::Read;
 
 mod permutation;
 
-use intcode::Computer;
+use intcode::{Circuit, Computer, Memory};
 use permutation::*;
 use std::cmp::max;
 
+fn thrust(memory: &Memory, phase: &[i64], feedback: bool) -> i64 {
+    let mut circuit = Circuit::new();
+
+    let amplifiers = phase
+        .iter()
+        .map(|p| circuit.machine(memory.clone(), &[*p]))
+        .collect::<Vec<usize>>();
+
+    if feedback {
+        circuit.ring(&amplifiers);
+    } else {
+        circuit.chain(&amplifiers);
+    }
+
+    circuit.input(amplifiers[0], 0);
+    circuit.run().unwrap();
+
+    circuit
+        .last_output(*amplifiers.last().unwrap())
+        .expect("no output from the amplifier")
+}
+
 fn main() {
     let mut input = String::new();
 
@@ -15,75 +37,22 @@ fn main() {
         .read_to_string(&mut input)
         .unwrap();
 
-    let mut data = [0, 1, 2, 3, 4];
-    let phases = Permutation::new(&mut data);
-
-    let mut in_out = 0;
-    let mut max_thrust = 0;
-
     let computer = Computer::from_tape(&input);
 
-    for phase in phases {
-        let mut computers = vec![
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-        ];
-
-        for (i, c) in computers.iter_mut().enumerate() {
-            c.push_input(phase[i] as i64);
-            c.push_input(in_out);
-
-            c.compute();
-
-            in_out = c.pop_output().expect("no output from the amplifier");
-        }
-
-        max_thrust = max(in_out, max_thrust);
+    let mut data = [0, 1, 2, 3, 4];
+    let mut max_thrust = 0;
 
-        in_out = 0;
+    for phase in Permutation::new(&mut data) {
+        max_thrust = max(thrust(computer.memory(), &phase, false), max_thrust);
     }
 
     println!("Maximum thrust: {}", max_thrust);
 
     let mut data = [5, 6, 7, 8, 9];
-    let phases = Permutation::new(&mut data);
-
-    let mut in_out = 0;
     let mut max_thrust = 0;
 
-    for phase in phases {
-        let mut computers = vec![
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-            computer.clone(),
-        ];
-
-        for (c, p) in computers.iter_mut().zip(phase.iter()) {
-            c.push_input(*p as i64);
-        }
-
-        while computers.first().unwrap().is_running() {
-            for c in computers.iter_mut() {
-                c.push_input(in_out);
-
-                c.compute_until_read();
-
-                if let Some(x) = c.pop_output() {
-                    in_out = x;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        max_thrust = max(in_out, max_thrust);
-
-        in_out = 0;
+    for phase in Permutation::new(&mut data) {
+        max_thrust = max(thrust(computer.memory(), &phase, true), max_thrust);
     }
 
     println!("Maximum thrust: {}", max_thrust);
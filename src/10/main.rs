@@ -2,7 +2,6 @@
 
 use std::cmp::Ordering;
 use std::collections::VecDeque;
-use std::convert::TryInto;
 use std::fs::File;
 use std::io::Read;
 
@@ -38,7 +37,10 @@ impl Space {
         let delta_x = x_2 as i64 - x_1 as i64;
         let delta_y = y_2 as i64 - y_1 as i64;
 
-        let gcd = gcd(delta_x.abs() as usize, delta_y.abs() as usize);
+        let gcd = gcd(
+            delta_x.unsigned_abs() as usize,
+            delta_y.unsigned_abs() as usize,
+        );
 
         let step_x = delta_x / gcd as i64;
         let step_y = delta_y / gcd as i64;
@@ -117,7 +119,7 @@ impl Space {
             }
         });
 
-        coordinates.try_into().unwrap()
+        coordinates.into()
     }
 }
 
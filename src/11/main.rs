@@ -19,7 +19,7 @@ fn main() {
 
     let painted = paint(&input, 0);
 
-    println!("Painted tiles: {}", painted.iter().count());
+    println!("Painted tiles: {}", painted.len());
 
     let painted = paint(&input, 1);
 
@@ -52,34 +52,30 @@ fn paint(tape: &str, input: i64) -> HashMap<(i32, i32), u8> {
 
     c.push_input(input);
 
-    c.compute_until_read();
+    while c.is_running() {
+        for (out, turn) in c.outputs().pairs() {
+            if out == 1 || painted.contains_key(&robot.position) {
+                painted.insert(robot.position, out as u8);
+            }
 
-    while let Some(out) = c.pop_output() {
-        if out == 1 || painted.get(&robot.position).is_some() {
-            painted.insert(robot.position, out as u8);
+            robot.rotation = match turn {
+                0 => (robot.rotation + 3) % 4,
+                1 => (robot.rotation + 1) % 4,
+                _ => panic!("illegal direction"),
+            };
+
+            robot.position = match robot.rotation {
+                0 => (robot.position.0, robot.position.1 + 1),
+                1 => (robot.position.0 + 1, robot.position.1),
+                2 => (robot.position.0, robot.position.1 - 1),
+                3 => (robot.position.0 - 1, robot.position.1),
+                _ => panic!("illegal robot rotation"),
+            };
         }
 
-        c.compute_until_io();
-
-        robot.rotation = match c.pop_output().unwrap() {
-            0 => (robot.rotation + 3) % 4,
-            1 => (robot.rotation + 1) % 4,
-            _ => panic!("illegal direction"),
-        };
-
-        robot.position = match robot.rotation {
-            0 => (robot.position.0, robot.position.1 + 1),
-            1 => (robot.position.0 + 1, robot.position.1),
-            2 => (robot.position.0, robot.position.1 - 1),
-            3 => (robot.position.0 - 1, robot.position.1),
-            _ => panic!("illegal robot rotation"),
-        };
-
         let color = painted.get(&robot.position).unwrap_or(&0);
 
         c.push_input(*color as i64);
-
-        c.compute_until_read();
     }
 
     painted
@@ -29,12 +29,12 @@ impl Moon {
     fn compute_energy(&self) -> usize {
         self.position
             .iter()
-            .map(|x| x.abs() as usize)
+            .map(|x| x.unsigned_abs() as usize)
             .sum::<usize>()
             * self
                 .velocity
                 .iter()
-                .map(|x| x.abs() as usize)
+                .map(|x| x.unsigned_abs() as usize)
                 .sum::<usize>()
     }
 }
@@ -102,7 +102,7 @@ fn simulate_step(moons: &mut Vec<Moon>) {
     *moons = moons
         .iter()
         .map(|m| {
-            let velocity = m.compute_velocity(&moons);
+            let velocity = m.compute_velocity(moons);
             let position = [
                 m.position[0] + velocity[0],
                 m.position[1] + velocity[1],
@@ -14,15 +14,12 @@ fn main() {
 
     let computer = Computer::from_tape(&input);
 
-    let mut c = computer.clone();
-
-    c.compute();
-
-    let mut screen = HashMap::new();
-
-    while let (Some(x), Some(y), Some(t)) = (c.pop_output(), c.pop_output(), c.pop_output()) {
-        screen.insert((x, y), t);
-    }
+    let screen: HashMap<(i64, i64), i64> = computer
+        .clone()
+        .outputs()
+        .triples()
+        .map(|(x, y, t)| ((x, y), t))
+        .collect();
 
     println!(
         "Block tiles on screen: {}",
@@ -34,29 +31,20 @@ fn main() {
     c.set(0, 2);
 
     let mut paddle_x = 0;
+    let mut ball_x = 0;
     let mut score = 0;
 
-    loop {
-        for _ in 0..3 {
-            c.compute_until_read();
-        }
-
-        let x_o = c.pop_output();
-        let y_o = c.pop_output();
-        let t_o = c.pop_output();
-
-        if let (Some(x), Some(y), Some(t)) = (x_o, y_o, t_o) {
+    while c.is_running() {
+        for (x, y, t) in c.outputs().triples() {
             match (x, y, t) {
                 (-1, 0, s) => score = s,
                 (a, _, 3) => paddle_x = a,
-                (a, _, 4) if paddle_x < a => c.push_input(1),
-                (a, _, 4) if paddle_x == a => c.push_input(0),
-                (a, _, 4) if paddle_x > a => c.push_input(-1),
+                (a, _, 4) => ball_x = a,
                 _ => {}
             }
-        } else {
-            break;
         }
+
+        c.push_input((ball_x - paddle_x).signum());
     }
 
     println!("Score: {}", score);
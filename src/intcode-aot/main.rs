@@ -0,0 +1,27 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+
+use intcode::{parse_tape, transpile};
+
+fn main() {
+    let mut tape = String::new();
+
+    match env::args().nth(1) {
+        Some(path) => File::open(&path)
+            .and_then(|mut f| f.read_to_string(&mut tape))
+            .unwrap_or_else(|e| {
+                eprintln!("{}: {}", path, e);
+                process::exit(1);
+            }),
+        None => io::stdin().read_to_string(&mut tape).unwrap(),
+    };
+
+    let memory = parse_tape(&tape).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    });
+
+    print!("{}", transpile(&memory));
+}
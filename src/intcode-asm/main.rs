@@ -0,0 +1,34 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+
+use intcode::assemble;
+
+fn main() {
+    let mut source = String::new();
+
+    match env::args().nth(1) {
+        Some(path) => File::open(&path)
+            .and_then(|mut f| f.read_to_string(&mut source))
+            .unwrap_or_else(|e| {
+                eprintln!("{}: {}", path, e);
+                process::exit(1);
+            }),
+        None => io::stdin().read_to_string(&mut source).unwrap(),
+    };
+
+    match assemble(&source) {
+        Ok(tape) => println!(
+            "{}",
+            tape.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        ),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
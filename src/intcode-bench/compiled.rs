@@ -0,0 +1,248 @@
+// Generated by intcode-aot. Do not edit.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::mem;
+
+use intcode::{Computer, Overflow, State};
+
+const TAPE: [i64; 29] = [
+    109, 100, 21201, 0, 3, 0, 21202, 0,
+    7, 1, 22207, 1, 0, 2, 21208, 2,
+    0, 2, 1001, 28, -1, 28, 1005, 28,
+    2, 204, 0, 99, 0,
+];
+
+const CODE: [(usize, usize); 1] = [(0, 28)];
+
+// Writes at or above this address are left to the interpreter's paged memory.
+const MAX_ADDRESS: usize = 1 << 20;
+
+#[derive(Clone)]
+pub struct Machine {
+    memory: Vec<i64>,
+    ip: usize,
+    rbp: i64,
+    halted: bool,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    overflow: Overflow,
+    fallback: Option<Computer>,
+}
+
+impl Default for Machine {
+    fn default() -> Machine {
+        Machine::new()
+    }
+}
+
+impl Machine {
+    pub fn new() -> Machine {
+        Machine {
+            memory: TAPE.to_vec(),
+            ip: 0,
+            rbp: 0,
+            halted: false,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            overflow: Overflow::default(),
+            fallback: None,
+        }
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.push_input(value),
+            None => self.input.push_back(value),
+        }
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.pop_output(),
+            None => self.output.pop_front(),
+        }
+    }
+
+    pub fn get(&self, addr: usize) -> Option<i64> {
+        match self.fallback.as_ref() {
+            Some(computer) => computer.get(addr),
+            None => self.memory.get(addr).copied(),
+        }
+    }
+
+    pub fn set(&mut self, addr: usize, value: i64) {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.set(addr, value),
+            None if addr >= MAX_ADDRESS => {
+                self.fall_back(self.ip);
+                self.set(addr, value);
+            }
+            None => {
+                if self.store(addr, value) {
+                    self.fall_back(self.ip);
+                }
+            }
+        }
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+
+        if let Some(computer) = self.fallback.as_mut() {
+            computer.set_overflow(overflow);
+        }
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        match self.fallback.as_ref() {
+            Some(computer) => computer.overflow(),
+            None => self.overflow,
+        }
+    }
+
+    pub fn is_interpreted(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    pub fn compute(&mut self) -> State {
+        if self.fallback.is_none() {
+            match self.run() {
+                Ok(state) => return state,
+                Err(ip) => self.fall_back(ip),
+            }
+        }
+
+        self.fallback.as_mut().unwrap().compute()
+    }
+
+    fn fall_back(&mut self, ip: usize) {
+        let mut computer = Computer::with_io(
+            mem::take(&mut self.memory),
+            mem::take(&mut self.input),
+            mem::take(&mut self.output),
+        );
+
+        computer.set_ip(ip);
+        computer.set_rbp(self.rbp);
+        computer.set_overflow(self.overflow);
+
+        self.fallback = Some(computer);
+    }
+
+    fn peek(&self, addr: usize) -> i64 {
+        self.memory.get(addr).copied().unwrap_or(0)
+    }
+
+    fn load(&self, ip: usize, addr: i64) -> Result<i64, usize> {
+        match addr {
+            x if x < 0 => Err(ip),
+            x => Ok(self.peek(x as usize)),
+        }
+    }
+
+    fn add(&self, ip: usize, a: i64, b: i64) -> Result<i64, usize> {
+        self.overflow.add(a, b).ok_or(ip)
+    }
+
+    fn mul(&self, ip: usize, a: i64, b: i64) -> Result<i64, usize> {
+        self.overflow.mul(a, b).ok_or(ip)
+    }
+
+    fn relative(&self, ip: usize, offset: i64) -> Result<i64, usize> {
+        self.add(ip, self.rbp, offset)
+    }
+
+    fn address(ip: usize, addr: i64) -> Result<usize, usize> {
+        match addr {
+            x if x < 0 || x as usize >= MAX_ADDRESS => Err(ip),
+            x => Ok(x as usize),
+        }
+    }
+
+    fn store(&mut self, addr: usize, value: i64) -> bool {
+        if addr >= self.memory.len() {
+            self.memory.resize(addr + 1, 0);
+        }
+
+        self.memory[addr] = value;
+
+        CODE.iter().any(|(start, end)| (*start..*end).contains(&addr))
+    }
+
+    fn run(&mut self) -> Result<State, usize> {
+        if self.halted {
+            return Ok(State::Halted);
+        }
+
+        loop {
+            match self.ip {
+                0 => {
+                    // 0000: ARB #100
+                    self.rbp = self.relative(0, 100)?;
+
+                    self.ip = 2;
+                }
+                2 => {
+                    // 0002: ADD [rb+0], #3 -> [rb+0]
+                    let a = self.load(2, self.rbp)?;
+                    let b = 3;
+                    let d = Self::address(2, self.rbp)?;
+                    if self.store(d, self.add(2, a, b)?) {
+                        return Err(6);
+                    }
+
+                    // 0006: MUL [rb+0], #7 -> [rb+1]
+                    let a = self.load(6, self.rbp)?;
+                    let b = 7;
+                    let d = Self::address(6, self.relative(6, 1)?)?;
+                    if self.store(d, self.mul(6, a, b)?) {
+                        return Err(10);
+                    }
+
+                    // 0010: LT [rb+1], [rb+0] -> [rb+2]
+                    let a = self.load(10, self.relative(10, 1)?)?;
+                    let b = self.load(10, self.rbp)?;
+                    let d = Self::address(10, self.relative(10, 2)?)?;
+                    if self.store(d, (a < b) as i64) {
+                        return Err(14);
+                    }
+
+                    // 0014: EQ [rb+2], #0 -> [rb+2]
+                    let a = self.load(14, self.relative(14, 2)?)?;
+                    let b = 0;
+                    let d = Self::address(14, self.relative(14, 2)?)?;
+                    if self.store(d, (a == b) as i64) {
+                        return Err(18);
+                    }
+
+                    // 0018: ADD [28], #-1 -> [28]
+                    let a = self.peek(28);
+                    let b = -1;
+                    let d = 28;
+                    if self.store(d, self.add(18, a, b)?) {
+                        return Err(22);
+                    }
+
+                    // 0022: JNZ [28], #2
+                    self.ip = if self.peek(28) != 0 {
+                        2
+                    } else {
+                        25
+                    };
+                }
+                25 => {
+                    // 0025: OUT [rb+0]
+                    let x = self.load(25, self.rbp)?;
+                    self.output.push_back(x);
+
+                    // 0027: HLT
+                    self.ip = 27;
+                    self.halted = true;
+                    return Ok(State::Halted);
+                }
+                ip => return Err(ip),
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+        ARB #100
+loop:   ADD [rb+0], #3 -> [rb+0]
+        MUL [rb+0], #7 -> [rb+1]
+        LT [rb+1], [rb+0] -> [rb+2]
+        EQ [rb+2], #0 -> [rb+2]
+        ADD [n], #-1 -> [n]
+        JNZ [n], #loop
+        OUT [rb+0]
+        HLT
+n:      .data 0
@@ -0,0 +1,127 @@
+use std::time::Instant;
+
+#[rustfmt::skip]
+mod compiled;
+mod reference;
+
+#[cfg(test)]
+use intcode::transpile;
+use intcode::{assemble, Computer, Memory};
+
+const LOOP: &str = include_str!("loop.asm");
+
+fn report(name: &str, steps: u64, seconds: f64, baseline: Option<f64>) -> f64 {
+    let rate = steps as f64 / seconds;
+
+    match baseline {
+        Some(x) => println!(
+            "  {:<10} {:>8.1} M steps/s  ({:.2}x)",
+            name,
+            rate / 1e6,
+            rate / x
+        ),
+        None => println!("  {:<10} {:>8.1} M steps/s", name, rate / 1e6),
+    }
+
+    rate
+}
+
+fn bench(name: &str, tape: &[i64], iterations: usize) {
+    println!("{}: {} run(s)", name, iterations);
+
+    let reference = reference::Computer::new(tape.to_vec());
+    let mut steps = 0;
+    let mut expected = None;
+
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        let mut c = reference.clone();
+        c.compute();
+        steps += c.steps();
+        expected = c.pop_output();
+    }
+
+    let baseline = report("reference", steps, start.elapsed().as_secs_f64(), None);
+
+    for (kind, memory) in &[
+        ("dense", Memory::dense(tape.to_vec())),
+        ("paged", Memory::paged(tape.to_vec())),
+    ] {
+        let computer = Computer::new(memory.clone());
+
+        let start = Instant::now();
+
+        for _ in 0..iterations {
+            let mut c = computer.clone();
+            c.compute();
+            assert_eq!(c.pop_output(), expected, "results differ from reference");
+        }
+
+        report(kind, steps, start.elapsed().as_secs_f64(), Some(baseline));
+    }
+
+    let mut machine = compiled::Machine::new();
+
+    for (addr, x) in tape.iter().enumerate() {
+        if machine.get(addr) != Some(*x) {
+            machine.set(addr, *x);
+        }
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        let mut m = machine.clone();
+        m.compute();
+        assert_eq!(m.pop_output(), expected, "results differ from reference");
+        assert!(
+            !m.is_interpreted(),
+            "compiled code fell back to the interpreter"
+        );
+    }
+
+    report(
+        "compiled",
+        steps,
+        start.elapsed().as_secs_f64(),
+        Some(baseline),
+    );
+}
+
+fn main() {
+    let mut tape = assemble(LOOP).unwrap();
+    let n = tape.len() - 1;
+
+    tape[n] = 10_000_000;
+    bench("long loop", &tape, 1);
+
+    tape[n] = 10;
+    bench("10,000 short clones", &tape, 10_000);
+}
+
+#[test]
+fn test_compiled_is_current() {
+    assert_eq!(
+        transpile(&assemble(LOOP).unwrap()),
+        include_str!("compiled.rs"),
+        "regenerate with: intcode-asm src/intcode-bench/loop.asm | intcode-aot"
+    );
+}
+
+#[test]
+fn test_compiled_far_write() {
+    let mut m = compiled::Machine::new();
+    m.set(1 << 40, 5);
+    assert!(m.is_interpreted());
+    assert_eq!(m.get(1 << 40), Some(5));
+}
+
+#[test]
+fn test_compiled_overflow() {
+    let mut m = compiled::Machine::new();
+    m.set_overflow(intcode::Overflow::Checked);
+    m.set(1 << 40, 5);
+    assert!(m.is_interpreted());
+    assert_eq!(m.overflow(), intcode::Overflow::Checked);
+}
@@ -1,3 +1,5 @@
+// Baseline interpreter from before instructions were cached, kept for comparison.
+
 use std::collections::VecDeque;
 
 #[derive(Clone)]
@@ -8,6 +10,7 @@ pub struct Computer {
     is_running: bool,
     input: VecDeque<i64>,
     output: VecDeque<i64>,
+    steps: u64,
 }
 
 pub enum Instruction {
@@ -39,33 +42,16 @@ impl Computer {
             is_running: true,
             input: VecDeque::new(),
             output: VecDeque::new(),
+            steps: 0,
         }
     }
 
-    pub fn from_tape(tape: &str) -> Computer {
-        let memory: Vec<i64> = tape.split(',').map(|x| x.parse::<i64>().unwrap()).collect();
-
-        Computer::new(memory)
-    }
-
-    pub fn push_input(&mut self, input: i64) {
-        self.input.push_back(input);
-    }
-
     pub fn pop_output(&mut self) -> Option<i64> {
         self.output.pop_front()
     }
 
-    pub fn get(&self, index: usize) -> Option<i64> {
-        self.memory.get(index).copied()
-    }
-
-    pub fn set(&mut self, index: usize, val: i64) {
-        self.memory[index] = val;
-    }
-
-    pub fn is_running(&self) -> bool {
-        self.is_running
+    pub fn steps(&self) -> u64 {
+        self.steps
     }
 
     pub fn compute(&mut self) {
@@ -74,25 +60,9 @@ impl Computer {
         }
     }
 
-    pub fn compute_until_read(&mut self) {
-        while self.is_running {
-            if let Instruction::Read { .. } = self.step() {
-                break;
-            }
-        }
-    }
-
-    pub fn compute_until_io(&mut self) {
-        while self.is_running {
-            match self.step() {
-                Instruction::Read { .. } => break,
-                Instruction::Write { .. } => break,
-                _ => {}
-            }
-        }
-    }
-
     fn step(&mut self) -> Instruction {
+        self.steps += 1;
+
         let instruction = self.fetch_and_decode();
 
         self.execute(&instruction);
@@ -240,63 +210,3 @@ impl Computer {
         }
     }
 }
-
-#[test]
-fn test_position_equals() {
-    let mut computer = Computer::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]);
-    computer.push_input(8);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(1));
-    assert_eq!(computer.pop_output(), None);
-
-    let mut computer = Computer::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]);
-    computer.push_input(9);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(0));
-    assert_eq!(computer.pop_output(), None);
-}
-
-#[test]
-fn test_position_less_than() {
-    let mut computer = Computer::new(vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8]);
-    computer.push_input(7);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(1));
-    assert_eq!(computer.pop_output(), None);
-
-    let mut computer = Computer::new(vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8]);
-    computer.push_input(8);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(0));
-    assert_eq!(computer.pop_output(), None);
-}
-
-#[test]
-fn test_immediate_equals() {
-    let mut computer = Computer::new(vec![3, 3, 1108, -1, 8, 3, 4, 3, 99]);
-    computer.push_input(8);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(1));
-    assert_eq!(computer.pop_output(), None);
-
-    let mut computer = Computer::new(vec![3, 3, 1108, -1, 8, 3, 4, 3, 99]);
-    computer.push_input(9);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(0));
-    assert_eq!(computer.pop_output(), None);
-}
-
-#[test]
-fn test_immediate_less_than() {
-    let mut computer = Computer::new(vec![3, 3, 1107, -1, 8, 3, 4, 3, 99]);
-    computer.push_input(7);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(1));
-    assert_eq!(computer.pop_output(), None);
-
-    let mut computer = Computer::new(vec![3, 3, 1107, -1, 8, 3, 4, 3, 99]);
-    computer.push_input(8);
-    computer.compute();
-    assert_eq!(computer.pop_output(), Some(0));
-    assert_eq!(computer.pop_output(), None);
-}
@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+use std::process;
+
+use intcode::{Computer, ErrorKind, Opcode};
+
+const HELP: &str = "\
+commands:
+  s, step [n]          execute n instructions (default 1)
+  c, continue          run until a breakpoint, halt, error or missing input
+  b, break <ip>        set breakpoint on ip
+  bo <opcode>          break before executing opcode (number or mnemonic)
+  d, delete <ip>       remove breakpoint on ip
+  do <opcode>          remove opcode breakpoint
+  bl                   list breakpoints
+  p, print <addr> [n]  print n memory cells starting at addr
+  set <addr> <value>   write memory cell
+  r, regs              show ip, rbp and state
+  l, list [n]          disassemble n instructions from ip (default 5)
+  i, input <v>...      push input values
+  o, output            drain output queue
+  q, quit              exit";
+
+struct Debugger {
+    computer: Computer,
+    breakpoints: HashSet<usize>,
+    opcode_breakpoints: HashSet<Opcode>,
+}
+
+impl Debugger {
+    fn current(&self) -> String {
+        self.computer.memory().disassemble_at(self.computer.ip()).0
+    }
+
+    fn should_break(&self) -> bool {
+        if self.breakpoints.contains(&self.computer.ip()) {
+            return true;
+        }
+
+        match self.computer.memory().decode(self.computer.ip()) {
+            Some(op) => self.opcode_breakpoints.contains(&op.opcode),
+            None => false,
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        if !self.computer.is_running() {
+            println!("halted");
+            return false;
+        }
+
+        match self.computer.try_step() {
+            Ok(_) => true,
+            Err(e) if e.kind == ErrorKind::EmptyInput => {
+                println!("awaiting input");
+                false
+            }
+            Err(e) => {
+                println!("error: {}", e);
+                false
+            }
+        }
+    }
+
+    fn run(&mut self, steps: Option<usize>) {
+        let mut executed = 0;
+
+        while self.step() {
+            executed += 1;
+
+            if steps == Some(executed) {
+                break;
+            }
+
+            if steps.is_none() && self.should_break() {
+                println!("breakpoint");
+                break;
+            }
+        }
+
+        println!("{} instruction(s) executed", executed);
+
+        if self.computer.is_running() {
+            println!("{}", self.current());
+        }
+    }
+
+    fn command(&mut self, line: &str) -> Result<bool, String> {
+        let args = line.split_whitespace().collect::<Vec<&str>>();
+
+        let number = |i: usize| -> Result<i64, String> {
+            let arg = args.get(i).ok_or("missing argument")?;
+            arg.parse::<i64>()
+                .map_err(|_| format!("not a number: {}", arg))
+        };
+
+        let address = |i: usize| -> Result<usize, String> {
+            let addr = number(i)?;
+            if addr < 0 {
+                return Err(format!("negative address: {}", addr));
+            }
+            Ok(addr as usize)
+        };
+
+        let opcode = |i: usize| -> Result<Opcode, String> {
+            let arg = args.get(i).ok_or("missing argument")?;
+            arg.parse::<i64>()
+                .ok()
+                .and_then(Opcode::from_i64)
+                .or_else(|| Opcode::from_mnemonic(arg))
+                .ok_or(format!("unknown opcode: {}", arg))
+        };
+
+        match args.first().copied() {
+            None => {}
+            Some("s") | Some("step") => {
+                let steps = if args.len() > 1 { address(1)? } else { 1 };
+                if steps == 0 {
+                    return Err("step count must be at least 1".to_string());
+                }
+                self.run(Some(steps));
+            }
+            Some("c") | Some("continue") => self.run(None),
+            Some("b") | Some("break") => {
+                self.breakpoints.insert(address(1)?);
+            }
+            Some("bo") => {
+                self.opcode_breakpoints.insert(opcode(1)?);
+            }
+            Some("d") | Some("delete") => {
+                let removed = self.breakpoints.remove(&address(1)?);
+
+                if !removed {
+                    return Err("no such breakpoint".to_string());
+                }
+            }
+            Some("do") => {
+                let removed = self.opcode_breakpoints.remove(&opcode(1)?);
+
+                if !removed {
+                    return Err("no such opcode breakpoint".to_string());
+                }
+            }
+            Some("bl") => {
+                let mut breakpoints = self.breakpoints.iter().collect::<Vec<&usize>>();
+                breakpoints.sort();
+
+                for ip in breakpoints {
+                    println!("ip {:04}", ip);
+                }
+
+                for opcode in &self.opcode_breakpoints {
+                    println!("opcode {} ({})", opcode, opcode.value());
+                }
+            }
+            Some("p") | Some("print") => {
+                let addr = address(1)?;
+                let len = if args.len() > 2 { address(2)? } else { 1 };
+
+                for i in addr..addr + len {
+                    println!("{:04}: {}", i, self.computer.get(i).unwrap_or(0));
+                }
+            }
+            Some("set") => self.computer.set(address(1)?, number(2)?),
+            Some("r") | Some("regs") => {
+                println!(
+                    "ip: {}, rbp: {}, running: {}",
+                    self.computer.ip(),
+                    self.computer.rbp(),
+                    self.computer.is_running()
+                );
+                println!("{}", self.current());
+            }
+            Some("l") | Some("list") => {
+                let count = if args.len() > 1 { address(1)? } else { 5 };
+                let mut addr = self.computer.ip();
+
+                for _ in 0..count {
+                    let (line, len) = self.computer.memory().disassemble_at(addr);
+                    println!("{}", line);
+                    addr += len;
+                }
+            }
+            Some("i") | Some("input") => {
+                for i in 1..args.len() {
+                    self.computer.push_input(number(i)?);
+                }
+            }
+            Some("o") | Some("output") => {
+                while let Some(x) = self.computer.pop_output() {
+                    println!("{}", x);
+                }
+            }
+            Some("h") | Some("help") => println!("{}", HELP),
+            Some("q") | Some("quit") => return Ok(false),
+            Some(x) => return Err(format!("unknown command: {}", x)),
+        }
+
+        Ok(true)
+    }
+}
+
+fn main() {
+    let path = env::args().nth(1).unwrap_or_else(|| {
+        eprintln!("usage: intcode-dbg <tape>");
+        process::exit(1);
+    });
+
+    let mut input = String::new();
+
+    File::open(&path)
+        .and_then(|mut f| f.read_to_string(&mut input))
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        });
+
+    let computer = Computer::try_from_tape(&input).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        process::exit(1);
+    });
+
+    let mut debugger = Debugger {
+        computer,
+        breakpoints: HashSet::new(),
+        opcode_breakpoints: HashSet::new(),
+    };
+
+    println!("{}", debugger.current());
+
+    let stdin = io::stdin();
+
+    loop {
+        print!("(dbg) ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+
+        match debugger.command(&line) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
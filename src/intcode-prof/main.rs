@@ -0,0 +1,56 @@
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::process;
+
+use intcode::{Computer, Profiler, State};
+
+const HOTTEST: usize = 20;
+
+fn main() {
+    let args = env::args().collect::<Vec<String>>();
+
+    if args.len() < 2 {
+        eprintln!("usage: intcode-prof <tape> [input,...]");
+        process::exit(1);
+    }
+
+    let mut tape = String::new();
+
+    File::open(&args[1])
+        .and_then(|mut f| f.read_to_string(&mut tape))
+        .unwrap_or_else(|e| {
+            eprintln!("{}: {}", args[1], e);
+            process::exit(1);
+        });
+
+    let mut computer = Computer::try_from_tape(&tape).unwrap_or_else(|e| {
+        eprintln!("{}: {}", args[1], e);
+        process::exit(1);
+    });
+    computer.profile(Profiler::new());
+
+    for x in args.iter().skip(2).flat_map(|x| x.split(',')) {
+        computer.push_input(x.trim().parse().unwrap_or_else(|_| {
+            eprintln!("not a number: {}", x);
+            process::exit(1);
+        }));
+    }
+
+    match computer.try_compute() {
+        Ok(State::Halted) => {}
+        Ok(state) => println!("stopped: {:?}", state),
+        Err(e) => println!("error: {}", e),
+    }
+
+    let output = computer
+        .output()
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>();
+
+    println!("output: {}\n", output.join(","));
+
+    let profiler = computer.take_profiler().unwrap();
+    print!("{}", profiler.report(computer.memory(), HOTTEST));
+}
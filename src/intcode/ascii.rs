@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Computer;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsciiError {
+    NonAsciiChar { index: usize, ch: char },
+    NonAsciiValue { index: usize, value: i64 },
+}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsciiError::NonAsciiChar { index, ch } => {
+                write!(f, "non-ASCII character {:?} at index {}", ch, index)
+            }
+            AsciiError::NonAsciiValue { index, value } => {
+                write!(f, "non-ASCII output value {} at index {}", value, index)
+            }
+        }
+    }
+}
+
+impl Error for AsciiError {}
+
+fn to_text<'a>(values: impl Iterator<Item = &'a i64>) -> Result<String, AsciiError> {
+    values
+        .enumerate()
+        .map(|(index, value)| match *value {
+            x @ 0..=127 => Ok(x as u8 as char),
+            value => Err(AsciiError::NonAsciiValue { index, value }),
+        })
+        .collect()
+}
+
+impl Computer {
+    pub fn push_line(&mut self, line: &str) -> Result<(), AsciiError> {
+        if let Some((index, ch)) = line.char_indices().find(|x| !x.1.is_ascii()) {
+            return Err(AsciiError::NonAsciiChar { index, ch });
+        }
+
+        for b in line.bytes().chain(Some(b'\n')) {
+            self.push_input(b as i64);
+        }
+
+        Ok(())
+    }
+
+    pub fn read_line(&mut self) -> Result<Option<String>, AsciiError> {
+        let len = match self.output.iter().position(|x| *x == '\n' as i64) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+
+        let line = to_text(self.output.iter().take(len))?;
+        self.output.drain(..=len);
+
+        Ok(Some(line))
+    }
+
+    pub fn drain_text(&mut self) -> Result<String, AsciiError> {
+        let text = to_text(self.output.iter())?;
+        self.output.clear();
+
+        Ok(text)
+    }
+
+    pub fn split_text(&mut self) -> Result<(String, Option<i64>), AsciiError> {
+        let value = match self.output.back() {
+            Some(x) if !(0..=127).contains(x) => Some(*x),
+            _ => None,
+        };
+
+        let len = self.output.len() - value.is_some() as usize;
+        let text = to_text(self.output.iter().take(len))?;
+        self.output.clear();
+
+        Ok((text, value))
+    }
+}
+
+#[test]
+fn test_ascii() {
+    let mut computer = Computer::new(vec![3, 7, 4, 7, 1105, 1, 0, 0]);
+
+    computer.push_line("hello").unwrap();
+    computer.push_line("world").unwrap();
+    assert_eq!(
+        computer.push_line("naïve"),
+        Err(AsciiError::NonAsciiChar { index: 2, ch: 'ï' })
+    );
+    computer.compute();
+
+    assert_eq!(computer.read_line(), Ok(Some("hello".to_string())));
+    assert_eq!(computer.drain_text(), Ok("world\n".to_string()));
+    assert_eq!(computer.read_line(), Ok(None));
+
+    let mut tape = "map\n#.#\n"
+        .bytes()
+        .flat_map(|x| vec![104, x as i64])
+        .collect::<Vec<i64>>();
+    tape.extend(vec![104, 19_349_722, 104, -1, 104, 65, 99]);
+
+    let mut computer = Computer::new(tape);
+    computer.compute();
+    assert_eq!(computer.read_line(), Ok(Some("map".to_string())));
+    assert_eq!(
+        computer.split_text(),
+        Err(AsciiError::NonAsciiValue {
+            index: 4,
+            value: 19_349_722
+        })
+    );
+
+    let mut computer = Computer::new(vec![104, 35, 104, 10, 104, 19_349_722, 99]);
+    computer.compute();
+    assert_eq!(
+        computer.split_text(),
+        Ok(("#\n".to_string(), Some(19_349_722)))
+    );
+    assert_eq!(computer.split_text(), Ok((String::new(), None)));
+}
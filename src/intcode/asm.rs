@@ -0,0 +1,524 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::op::{Op, Opcode, Operand};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for AsmError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Str(String),
+    Colon,
+    Comma,
+    Arrow,
+    Hash,
+    LBracket,
+    RBracket,
+    Plus,
+    Minus,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+enum Atom {
+    Number(i64, usize),
+    Label(String, usize),
+}
+
+type Expr = Vec<(i64, Atom)>;
+
+enum Item {
+    Op {
+        opcode: Opcode,
+        operands: Vec<(Mode, Expr)>,
+    },
+    Data(Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    line: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn new(line: usize, text: &str) -> Result<Parser, AsmError> {
+        let chars = text.chars().collect::<Vec<char>>();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        let error = |column: usize, message: String| AsmError {
+            line,
+            column,
+            message,
+        };
+
+        while i < chars.len() {
+            let column = i + 1;
+
+            let token = match chars[i] {
+                ';' => break,
+                c if c.is_whitespace() => {
+                    i += 1;
+                    continue;
+                }
+                ':' => Token::Colon,
+                ',' => Token::Comma,
+                '#' => Token::Hash,
+                '[' => Token::LBracket,
+                ']' => Token::RBracket,
+                '+' => Token::Plus,
+                '-' if chars.get(i + 1) == Some(&'>') => {
+                    i += 1;
+                    Token::Arrow
+                }
+                '-' => Token::Minus,
+                '"' => {
+                    let mut s = String::new();
+
+                    loop {
+                        i += 1;
+
+                        match chars.get(i) {
+                            None => return Err(error(column, "unterminated string".to_string())),
+                            Some('"') => break,
+                            Some('\\') => {
+                                i += 1;
+
+                                s.push(match chars.get(i) {
+                                    Some('n') => '\n',
+                                    Some('t') => '\t',
+                                    Some('0') => '\0',
+                                    Some('\\') => '\\',
+                                    Some('"') => '"',
+                                    _ => return Err(error(i + 1, "illegal escape".to_string())),
+                                });
+                            }
+                            Some(c) => s.push(*c),
+                        }
+                    }
+
+                    Token::Str(s)
+                }
+                c if c.is_ascii_digit() => {
+                    let start = i;
+
+                    while chars.get(i + 1).is_some_and(|x| x.is_ascii_digit()) {
+                        i += 1;
+                    }
+
+                    let digits = chars[start..=i].iter().collect::<String>();
+
+                    match digits.parse() {
+                        Ok(x) => Token::Number(x),
+                        Err(_) => return Err(error(column, format!("illegal number {}", digits))),
+                    }
+                }
+                c if c.is_alphabetic() || c == '_' || c == '.' => {
+                    let start = i;
+
+                    while chars
+                        .get(i + 1)
+                        .is_some_and(|x| x.is_alphanumeric() || *x == '_')
+                    {
+                        i += 1;
+                    }
+
+                    Token::Ident(chars[start..=i].iter().collect())
+                }
+                c => return Err(error(column, format!("unexpected character '{}'", c))),
+            };
+
+            tokens.push((column, token));
+            i += 1;
+        }
+
+        Ok(Parser {
+            tokens,
+            pos: 0,
+            line,
+            end: chars.len() + 1,
+        })
+    }
+
+    fn column(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |x| x.0)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|x| &x.1)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|x| &x.1)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, message: &str) -> AsmError {
+        AsmError {
+            line: self.line,
+            column: self.column(),
+            message: message.to_string(),
+        }
+    }
+
+    fn expect(&mut self, token: Token, message: &str) -> Result<(), AsmError> {
+        if self.peek() == Some(&token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn expr(&mut self) -> Result<Expr, AsmError> {
+        let mut expr = Vec::new();
+        let mut sign = match self.peek() {
+            Some(Token::Minus) => {
+                self.pos += 1;
+                -1
+            }
+            Some(Token::Plus) => {
+                self.pos += 1;
+                1
+            }
+            _ => 1,
+        };
+
+        loop {
+            let column = self.column();
+
+            let atom = match self.next() {
+                Some(Token::Number(x)) => {
+                    let value = if sign < 0 {
+                        0i64.checked_sub_unsigned(x)
+                    } else {
+                        0i64.checked_add_unsigned(x)
+                    };
+
+                    match value {
+                        Some(value) => Atom::Number(value, column),
+                        None => {
+                            self.pos -= 1;
+                            return Err(self.error(&format!("number {} out of range", x)));
+                        }
+                    }
+                }
+                Some(Token::Ident(x)) => Atom::Label(x, column),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("expected number or label"));
+                }
+            };
+
+            expr.push((sign, atom));
+
+            sign = match self.peek() {
+                Some(Token::Plus) => 1,
+                Some(Token::Minus) => -1,
+                _ => return Ok(expr),
+            };
+
+            self.pos += 1;
+        }
+    }
+
+    fn operand(&mut self) -> Result<(Mode, Expr), AsmError> {
+        match self.peek() {
+            Some(Token::Hash) => {
+                self.pos += 1;
+                Ok((Mode::Immediate, self.expr()?))
+            }
+            Some(Token::LBracket) => {
+                self.pos += 1;
+
+                let operand = match (self.peek(), self.peek_at(1)) {
+                    (Some(Token::Ident(x)), Some(Token::RBracket)) if x == "rb" => {
+                        self.pos += 1;
+                        (Mode::Relative, vec![(1, Atom::Number(0, 0))])
+                    }
+                    (Some(Token::Ident(x)), Some(Token::Plus))
+                    | (Some(Token::Ident(x)), Some(Token::Minus))
+                        if x == "rb" =>
+                    {
+                        self.pos += 1;
+                        (Mode::Relative, self.expr()?)
+                    }
+                    _ => (Mode::Position, self.expr()?),
+                };
+
+                self.expect(Token::RBracket, "expected ']'")?;
+
+                Ok(operand)
+            }
+            _ => Err(self.error("expected operand")),
+        }
+    }
+
+    fn item(&mut self, mnemonic: &str) -> Result<Item, AsmError> {
+        if mnemonic.eq_ignore_ascii_case(".data") || mnemonic.eq_ignore_ascii_case("data") {
+            let mut words = Vec::new();
+
+            loop {
+                if let Some(Token::Str(s)) = self.peek() {
+                    if let Some(c) = s.chars().find(|c| !c.is_ascii()) {
+                        return Err(self.error(&format!("non-ASCII character '{}'", c)));
+                    }
+
+                    let column = self.column();
+                    words.extend(s.bytes().map(|x| vec![(1, Atom::Number(x as i64, column))]));
+                    self.pos += 1;
+                } else {
+                    words.push(self.expr()?);
+                }
+
+                if self.at_end() {
+                    return Ok(Item::Data(words));
+                }
+
+                self.expect(Token::Comma, "expected ','")?;
+            }
+        }
+
+        let opcode = match Opcode::from_mnemonic(mnemonic) {
+            Some(opcode) => opcode,
+            None => {
+                self.pos -= 1;
+                return Err(self.error(&format!("unknown mnemonic {}", mnemonic)));
+            }
+        };
+
+        let mut operands = Vec::new();
+
+        while operands.len() < opcode.arity() {
+            if operands.len() + 1 == opcode.arity() && opcode.has_dst() {
+                if let Some(Token::Arrow) = self.peek() {
+                    self.pos += 1;
+                } else if !operands.is_empty() {
+                    self.expect(Token::Comma, "expected ',' or '->'")?;
+                }
+
+                let column = self.column();
+                let operand = self.operand()?;
+
+                if operand.0 == Mode::Immediate {
+                    return Err(AsmError {
+                        line: self.line,
+                        column,
+                        message: "dst operand cannot use immediate mode".to_string(),
+                    });
+                }
+
+                operands.push(operand);
+            } else {
+                if !operands.is_empty() {
+                    self.expect(Token::Comma, "expected ','")?;
+                }
+
+                operands.push(self.operand()?);
+            }
+        }
+
+        Ok(Item::Op { opcode, operands })
+    }
+}
+
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels: HashMap<String, i64> = HashMap::new();
+    let mut items = Vec::new();
+    let mut addr = 0;
+
+    for (i, text) in source.lines().enumerate() {
+        let mut parser = Parser::new(i + 1, text)?;
+
+        if let (Some(Token::Number(x)), Some(Token::Colon)) = (parser.peek(), parser.peek_at(1)) {
+            if *x != addr as u64 {
+                return Err(parser.error(&format!("address {} does not match {}", x, addr)));
+            }
+
+            parser.pos += 2;
+        }
+
+        while let (Some(Token::Ident(x)), Some(Token::Colon)) = (parser.peek(), parser.peek_at(1)) {
+            if labels.insert(x.clone(), addr as i64).is_some() {
+                return Err(parser.error(&format!("duplicate label {}", x)));
+            }
+
+            parser.pos += 2;
+        }
+
+        let item = match parser.next() {
+            None => continue,
+            Some(Token::Ident(mnemonic)) => parser.item(&mnemonic)?,
+            Some(_) => {
+                parser.pos -= 1;
+                return Err(parser.error("expected label, mnemonic or directive"));
+            }
+        };
+
+        if !parser.at_end() {
+            return Err(parser.error("unexpected trailing input"));
+        }
+
+        addr += match &item {
+            Item::Op { opcode, .. } => opcode.arity() + 1,
+            Item::Data(words) => words.len(),
+        };
+
+        items.push((i + 1, item));
+    }
+
+    let eval = |line: usize, expr: &Expr| -> Result<i64, AsmError> {
+        expr.iter().try_fold(0i64, |acc, (sign, atom)| {
+            let (value, column) = match atom {
+                Atom::Number(x, column) => (*x, *column),
+                Atom::Label(x, column) => match labels.get(x) {
+                    Some(addr) => (sign * addr, *column),
+                    None => {
+                        return Err(AsmError {
+                            line,
+                            column: *column,
+                            message: format!("undefined label {}", x),
+                        })
+                    }
+                },
+            };
+
+            acc.checked_add(value).ok_or_else(|| AsmError {
+                line,
+                column,
+                message: "arithmetic overflow".to_string(),
+            })
+        })
+    };
+
+    let mut tape = Vec::with_capacity(addr);
+
+    for (line, item) in items {
+        match item {
+            Item::Op { opcode, operands } => {
+                let operands = operands
+                    .iter()
+                    .map(|(mode, expr)| {
+                        let value = eval(line, expr)?;
+
+                        Ok(match mode {
+                            Mode::Position => Operand::Position(value),
+                            Mode::Immediate => Operand::Immediate(value),
+                            Mode::Relative => Operand::Relative(value),
+                        })
+                    })
+                    .collect::<Result<Vec<Operand>, AsmError>>()?;
+
+                tape.extend(Op::new(opcode, &operands).encode());
+            }
+            Item::Data(words) => {
+                for expr in words {
+                    tape.push(eval(line, &expr)?);
+                }
+            }
+        }
+    }
+
+    Ok(tape)
+}
+
+#[test]
+fn test_assemble() {
+    let source = r#"
+        ; echo input + 5 until zero
+        loop:   IN -> [n]
+                JZ [n], #end
+                ADD [n], #5 -> [rb+out]
+                OUT [rb+out]
+                JNZ #1, #loop
+        end:    HLT
+        n:      .data 0
+        out:    .data 0
+        msg:    .data "hi\n", -1, msg+1
+    "#;
+
+    let tape = assemble(source).unwrap();
+
+    assert_eq!(
+        tape,
+        vec![
+            3, 15, 1006, 15, 14, 21001, 15, 5, 16, 204, 16, 1105, 1, 0, 99, 0, 0, 104, 105, 10, -1,
+            18
+        ]
+    );
+
+    let mut computer = crate::Computer::new(tape.clone());
+    computer.push_input(3);
+    computer.push_input(0);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(8));
+    assert_eq!(computer.pop_output(), None);
+
+    let listing = crate::disassemble(&tape).join("\n");
+    assert_eq!(assemble(&listing).unwrap(), tape);
+
+    let tape = vec![1101, i64::MIN, i64::MAX, 7, 204, i64::MIN, 99, i64::MIN];
+    let listing = crate::disassemble(&tape).join("\n");
+    assert_eq!(assemble(&listing).unwrap(), tape);
+    assert_eq!(
+        assemble(".data -9223372036854775808, 1 - 9223372036854775808").unwrap(),
+        vec![i64::MIN, i64::MIN + 1]
+    );
+}
+
+#[test]
+fn test_assemble_errors() {
+    let error = assemble("IN -> [x]\n  ADD #1, #2 -> #3").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 17));
+
+    let error = assemble("  IN -> [x]").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 10));
+    assert_eq!(error.message, "undefined label x");
+
+    let error = assemble("FOO #1").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 1));
+
+    let error = assemble("OUT [rb+1").unwrap_err();
+    assert_eq!(error.to_string(), "1:10: expected ']'");
+
+    let error = assemble(".data 9223372036854775807 + 1").unwrap_err();
+    assert_eq!(error.to_string(), "1:29: arithmetic overflow");
+
+    let error = assemble(".data 9223372036854775808").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "1:7: number 9223372036854775808 out of range"
+    );
+}
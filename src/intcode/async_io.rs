@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::{Computer, IntcodeError, State};
+
+struct Channel {
+    queue: VecDeque<i64>,
+    capacity: usize,
+    senders: usize,
+    receiver: bool,
+    recv_waker: Option<Waker>,
+    send_wakers: Vec<Waker>,
+}
+
+impl Channel {
+    fn pop(&mut self) -> Option<i64> {
+        let value = self.queue.pop_front();
+
+        if value.is_some() {
+            self.send_wakers.drain(..).for_each(Waker::wake);
+        }
+
+        value
+    }
+}
+
+pub struct AsyncSender {
+    channel: Arc<Mutex<Channel>>,
+}
+
+pub struct AsyncReceiver {
+    channel: Arc<Mutex<Channel>>,
+}
+
+pub struct SendFuture<'a> {
+    sender: &'a AsyncSender,
+    value: i64,
+}
+
+pub struct RecvFuture<'a> {
+    receiver: &'a AsyncReceiver,
+}
+
+pub fn async_channel(capacity: usize) -> (AsyncSender, AsyncReceiver) {
+    assert!(capacity > 0, "channel capacity must be positive");
+
+    let channel = Arc::new(Mutex::new(Channel {
+        queue: VecDeque::new(),
+        capacity,
+        senders: 1,
+        receiver: true,
+        recv_waker: None,
+        send_wakers: Vec::new(),
+    }));
+
+    (
+        AsyncSender {
+            channel: channel.clone(),
+        },
+        AsyncReceiver { channel },
+    )
+}
+
+impl AsyncSender {
+    pub fn send(&self, value: i64) -> SendFuture<'_> {
+        SendFuture {
+            sender: self,
+            value,
+        }
+    }
+}
+
+impl Clone for AsyncSender {
+    fn clone(&self) -> AsyncSender {
+        self.channel.lock().unwrap().senders += 1;
+
+        AsyncSender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl Drop for AsyncSender {
+    fn drop(&mut self) {
+        let mut channel = self.channel.lock().unwrap();
+        channel.senders -= 1;
+
+        if channel.senders == 0 {
+            if let Some(waker) = channel.recv_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl AsyncReceiver {
+    pub fn recv(&self) -> RecvFuture<'_> {
+        RecvFuture { receiver: self }
+    }
+
+    pub fn try_recv(&self) -> Option<i64> {
+        self.channel.lock().unwrap().pop()
+    }
+}
+
+impl Drop for AsyncReceiver {
+    fn drop(&mut self) {
+        let mut channel = self.channel.lock().unwrap();
+        channel.receiver = false;
+        channel.send_wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
+impl Future for SendFuture<'_> {
+    type Output = Result<(), i64>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), i64>> {
+        let mut channel = self.sender.channel.lock().unwrap();
+
+        if !channel.receiver {
+            return Poll::Ready(Err(self.value));
+        }
+
+        if channel.queue.len() >= channel.capacity {
+            channel.send_wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        channel.queue.push_back(self.value);
+
+        if let Some(waker) = channel.recv_waker.take() {
+            waker.wake();
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Future for RecvFuture<'_> {
+    type Output = Option<i64>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<i64>> {
+        let mut channel = self.receiver.channel.lock().unwrap();
+
+        if let Some(value) = channel.pop() {
+            return Poll::Ready(Some(value));
+        }
+
+        if channel.senders == 0 {
+            return Poll::Ready(None);
+        }
+
+        channel.recv_waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl Computer {
+    pub async fn run_async(
+        &mut self,
+        input: &AsyncReceiver,
+        output: &AsyncSender,
+    ) -> Result<State, IntcodeError> {
+        while let Some(x) = self.pop_output() {
+            if output.send(x).await.is_err() {
+                return Ok(State::Output(x));
+            }
+        }
+
+        loop {
+            match self.try_compute_until_output()? {
+                State::Output(x) => {
+                    if output.send(x).await.is_err() {
+                        return Ok(State::Output(x));
+                    }
+                }
+                State::AwaitingInput => match input.recv().await {
+                    Some(x) => self.push_input(x),
+                    None => return Ok(State::AwaitingInput),
+                },
+                state => return Ok(state),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_async_machines() {
+    use crate::{block_on, Executor};
+
+    let double = vec![3, 11, 1002, 11, 2, 11, 4, 11, 1105, 1, 0, 0];
+    let increment = vec![3, 11, 1001, 11, 1, 11, 4, 11, 1105, 1, 0, 0];
+
+    let (input, a) = async_channel(1);
+    let (a_out, b) = async_channel(1);
+    let (b_out, output) = async_channel(1);
+
+    let mut executor = Executor::new();
+
+    let first = executor.spawn(async move {
+        let mut computer = Computer::new(double);
+        computer.run_async(&a, &a_out).await
+    });
+
+    let second = executor.spawn(async move {
+        let mut computer = Computer::new(increment);
+        computer.run_async(&b, &b_out).await
+    });
+
+    let results = executor.spawn(async move {
+        let mut results = Vec::new();
+
+        for x in 1..=5 {
+            input.send(x).await.unwrap();
+        }
+        drop(input);
+
+        while let Some(x) = output.recv().await {
+            results.push(x);
+        }
+
+        results
+    });
+
+    assert_eq!(executor.run(), 0);
+    assert_eq!(results.take(), Some(vec![3, 5, 7, 9, 11]));
+    assert_eq!(first.take(), Some(Ok(State::AwaitingInput)));
+    assert_eq!(second.take(), Some(Ok(State::AwaitingInput)));
+
+    let (input, receiver) = async_channel(2);
+    let (sender, output) = async_channel(1);
+    let mut computer = Computer::new(vec![104, 1, 104, 2, 99]);
+
+    let mut executor = Executor::new();
+    let task = executor.spawn(async move { computer.run_async(&receiver, &sender).await });
+    assert_eq!(executor.run(), 1);
+    assert_eq!(output.try_recv(), Some(1));
+    assert_eq!(output.try_recv(), None);
+    assert_eq!(executor.run(), 0);
+    assert_eq!(output.try_recv(), Some(2));
+    assert_eq!(task.take(), Some(Ok(State::Halted)));
+    drop(input);
+
+    let (input, receiver) = async_channel(4);
+    let (sender, output) = async_channel(4);
+    block_on(input.send(20)).unwrap();
+    drop(input);
+
+    let mut computer = Computer::new(vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0]);
+    assert_eq!(
+        block_on(computer.run_async(&receiver, &sender)),
+        Ok(State::Halted)
+    );
+    assert_eq!(output.try_recv(), Some(21));
+}
@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use crate::disasm::{disassemble_at, reachable};
+use crate::op::{decode, Op, Opcode, Operand};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Taken(usize),
+    Fallthrough(usize),
+    Unresolved,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub ops: Vec<(usize, Op)>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cfg {
+    blocks: BTreeMap<usize, BasicBlock>,
+    lines: BTreeMap<usize, String>,
+}
+
+impl BasicBlock {
+    pub fn end(&self) -> usize {
+        self.ops
+            .last()
+            .map_or(self.start, |(addr, op)| addr + op.size())
+    }
+}
+
+pub(crate) fn successors(addr: usize, op: &Op) -> Vec<Edge> {
+    let next = addr + op.size();
+
+    let (cond, dst) = match (op.opcode, op.operands()) {
+        (Opcode::Halt, _) => return Vec::new(),
+        (Opcode::JumpNotZero, [cond, dst]) | (Opcode::JumpZero, [cond, dst]) => (cond, dst),
+        _ => return vec![Edge::Fallthrough(next)],
+    };
+
+    let taken = match cond {
+        Operand::Immediate(x) => Some((*x != 0) == (op.opcode == Opcode::JumpNotZero)),
+        _ => None,
+    };
+
+    let mut edges = Vec::new();
+
+    if taken != Some(false) {
+        match dst {
+            Operand::Immediate(x) if *x >= 0 => edges.push(Edge::Taken(*x as usize)),
+            Operand::Immediate(_) => {}
+            _ => edges.push(Edge::Unresolved),
+        }
+    }
+
+    if taken != Some(true) {
+        edges.push(Edge::Fallthrough(next));
+    }
+
+    edges
+}
+
+impl Cfg {
+    pub fn new(memory: &[i64]) -> Cfg {
+        let code = reachable(memory);
+
+        let ops = code
+            .iter()
+            .map(|x| (*x, decode(memory, *x).unwrap()))
+            .collect::<BTreeMap<usize, Op>>();
+
+        let mut leaders = BTreeSet::new();
+
+        if code.contains(&0) {
+            leaders.insert(0);
+        }
+
+        for (addr, op) in ops.iter() {
+            let edges = successors(*addr, op);
+
+            if edges != [Edge::Fallthrough(addr + op.size())] {
+                for edge in edges {
+                    if let Edge::Taken(x) | Edge::Fallthrough(x) = edge {
+                        leaders.insert(x);
+                    }
+                }
+            }
+        }
+
+        let mut blocks = BTreeMap::new();
+
+        for start in leaders.iter().copied().filter(|x| ops.contains_key(x)) {
+            let mut block = BasicBlock {
+                start,
+                ops: Vec::new(),
+                edges: Vec::new(),
+            };
+
+            let mut addr = start;
+
+            while let Some(op) = ops.get(&addr) {
+                block.ops.push((addr, *op));
+
+                let edges = successors(addr, op);
+                let next = addr + op.size();
+
+                if edges != [Edge::Fallthrough(next)] || leaders.contains(&next) {
+                    block.edges = edges
+                        .into_iter()
+                        .filter(|x| match x {
+                            Edge::Taken(x) | Edge::Fallthrough(x) => ops.contains_key(x),
+                            Edge::Unresolved => true,
+                        })
+                        .collect();
+                    break;
+                }
+
+                addr = next;
+            }
+
+            blocks.insert(start, block);
+        }
+
+        let lines = code
+            .iter()
+            .map(|x| (*x, disassemble_at(memory, *x).0))
+            .collect();
+
+        Cfg { blocks, lines }
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &BasicBlock> {
+        self.blocks.values()
+    }
+
+    pub fn block(&self, start: usize) -> Option<&BasicBlock> {
+        self.blocks.get(&start)
+    }
+
+    pub fn line(&self, addr: usize) -> Option<&str> {
+        self.lines.get(&addr).map(|x| x.as_str())
+    }
+
+    pub fn unresolved(&self) -> Vec<usize> {
+        self.blocks()
+            .filter(|x| x.edges.contains(&Edge::Unresolved))
+            .map(|x| x.ops.last().unwrap().0)
+            .collect()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot =
+            String::from("digraph cfg {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+        for block in self.blocks() {
+            let label = block
+                .ops
+                .iter()
+                .map(|(addr, _)| format!("{}\\l", self.lines[addr].replace('"', "\\\"")))
+                .collect::<String>();
+
+            let _ = writeln!(dot, "    b{} [label=\"{}\"];", block.start, label);
+
+            for edge in block.edges.iter() {
+                let _ = match edge {
+                    Edge::Taken(x) => writeln!(dot, "    b{} -> b{} [label=\"taken\"];", block.start, x),
+                    Edge::Fallthrough(x) => writeln!(dot, "    b{} -> b{};", block.start, x),
+                    Edge::Unresolved => writeln!(
+                        dot,
+                        "    u{0} [label=\"?\", shape=diamond];\n    b{0} -> u{0} [label=\"indirect\", style=dashed];",
+                        block.start
+                    ),
+                };
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+#[test]
+fn test_cfg() {
+    let tape = [
+        3, 20, 1006, 20, 12, 1001, 20, -1, 20, 1105, 1, 2, 105, 1, 20, 99,
+    ];
+    let cfg = Cfg::new(&tape);
+
+    assert_eq!(
+        cfg.blocks()
+            .map(|x| (x.start, x.end(), x.edges.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, 2, vec![Edge::Fallthrough(2)]),
+            (2, 5, vec![Edge::Taken(12), Edge::Fallthrough(5)]),
+            (5, 12, vec![Edge::Taken(2)]),
+            (12, 15, vec![Edge::Unresolved]),
+        ]
+    );
+    assert_eq!(cfg.unresolved(), vec![12]);
+    assert_eq!(cfg.block(5).unwrap().ops.len(), 2);
+
+    let dot = cfg.to_dot();
+    assert!(dot.starts_with("digraph cfg {"));
+    assert!(dot.contains("b5 [label=\"0005: ADD [20], #-1 -> [20]\\l0009: JNZ #1, #2\\l\"];"));
+    assert!(dot.contains("b2 -> b12 [label=\"taken\"];"));
+    assert!(dot.contains("b12 -> u12 [label=\"indirect\", style=dashed];"));
+}
@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Computer, IntcodeError, Memory, State};
+
+#[derive(Clone, Default)]
+pub struct Circuit {
+    nodes: Vec<Node>,
+    edges: Vec<(usize, usize)>,
+}
+
+#[derive(Clone)]
+struct Node {
+    computer: Computer,
+    state: Option<State>,
+    outputs: Vec<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitError {
+    pub node: usize,
+    pub error: IntcodeError,
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "node {}: {}", self.node, self.error)
+    }
+}
+
+impl Error for CircuitError {}
+
+impl Circuit {
+    pub fn new() -> Circuit {
+        Circuit::default()
+    }
+
+    pub fn machine<M: Into<Memory>>(&mut self, memory: M, inputs: &[i64]) -> usize {
+        let mut computer = Computer::new(memory);
+
+        for x in inputs {
+            computer.push_input(*x);
+        }
+
+        self.nodes.push(Node {
+            computer,
+            state: None,
+            outputs: Vec::new(),
+        });
+
+        self.nodes.len() - 1
+    }
+
+    pub fn connect(&mut self, from: usize, to: usize) {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "no such node"
+        );
+
+        self.edges.push((from, to));
+    }
+
+    pub fn chain(&mut self, nodes: &[usize]) {
+        for pair in nodes.windows(2) {
+            self.connect(pair[0], pair[1]);
+        }
+    }
+
+    pub fn ring(&mut self, nodes: &[usize]) {
+        self.chain(nodes);
+
+        if let (Some(first), Some(last)) = (nodes.first(), nodes.last()) {
+            self.connect(*last, *first);
+        }
+    }
+
+    pub fn fan_out(&mut self, from: usize, to: &[usize]) {
+        for x in to {
+            self.connect(from, *x);
+        }
+    }
+
+    pub fn input(&mut self, node: usize, value: i64) {
+        self.nodes[node].computer.push_input(value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn state(&self, node: usize) -> Option<State> {
+        self.nodes[node].state
+    }
+
+    pub fn outputs(&self, node: usize) -> &[i64] {
+        &self.nodes[node].outputs
+    }
+
+    pub fn last_output(&self, node: usize) -> Option<i64> {
+        self.nodes[node].outputs.last().copied()
+    }
+
+    pub fn run(&mut self) -> Result<(), CircuitError> {
+        loop {
+            let mut progress = false;
+
+            for node in 0..self.nodes.len() {
+                let computer = &mut self.nodes[node].computer;
+
+                if !computer.is_running() {
+                    continue;
+                }
+
+                let steps = computer.steps();
+
+                let state = computer
+                    .try_compute()
+                    .map_err(|error| CircuitError { node, error })?;
+
+                progress |= computer.steps() != steps;
+
+                let mut outputs = Vec::new();
+
+                while let Some(x) = computer.pop_output() {
+                    outputs.push(x);
+                }
+
+                self.nodes[node].state = Some(state);
+
+                for (from, to) in self.edges.iter() {
+                    if *from == node {
+                        for x in outputs.iter() {
+                            self.nodes[*to].computer.push_input(*x);
+                        }
+                    }
+                }
+
+                self.nodes[node].outputs.extend(outputs);
+            }
+
+            if !progress {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_circuit() {
+    let add = vec![3, 13, 3, 14, 1, 13, 14, 15, 4, 15, 1105, 1, 0, 0, 0, 0];
+    let echo = vec![3, 7, 4, 7, 1105, 1, 0, 0];
+
+    let mut circuit = Circuit::new();
+    let source = circuit.machine(echo.clone(), &[5]);
+    let left = circuit.machine(echo.clone(), &[]);
+    let right = circuit.machine(echo, &[]);
+    let sum = circuit.machine(add, &[]);
+
+    circuit.fan_out(source, &[left, right]);
+    circuit.connect(left, sum);
+    circuit.connect(right, sum);
+    circuit.input(source, 7);
+    circuit.run().unwrap();
+
+    assert_eq!(circuit.outputs(source), &[5, 7]);
+    assert_eq!(circuit.outputs(sum), &[12, 12]);
+    assert_eq!(circuit.state(sum), Some(State::AwaitingInput));
+
+    let mut circuit = Circuit::new();
+    let nodes = (0..3)
+        .map(|_| circuit.machine(vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0], &[]))
+        .collect::<Vec<usize>>();
+    circuit.ring(&nodes);
+    circuit.input(nodes[0], 10);
+    circuit.run().unwrap();
+
+    assert_eq!(circuit.last_output(nodes[2]), Some(13));
+    assert_eq!(circuit.state(nodes[0]), Some(State::Halted));
+
+    let mut circuit = Circuit::new();
+    let broken = circuit.machine(vec![42], &[]);
+    assert_eq!(circuit.run().unwrap_err().node, broken);
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+
+use crate::cfg::{successors, Edge};
+use crate::op::decode;
+
+pub fn disassemble_at(memory: &[i64], addr: usize) -> (String, usize) {
+    disassemble_words(memory.get(addr..).unwrap_or(&[]), addr)
+}
+
+pub(crate) fn disassemble_words(words: &[i64], addr: usize) -> (String, usize) {
+    match decode(words, 0) {
+        Some(op) => (format!("{:04}: {}", addr, op), op.size()),
+        None => (data(addr, words.first().copied().unwrap_or(0)), 1),
+    }
+}
+
+pub fn disassemble(memory: &[i64]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut addr = 0;
+
+    while addr < memory.len() {
+        let (line, len) = disassemble_at(memory, addr);
+
+        lines.push(line);
+        addr += len;
+    }
+
+    lines
+}
+
+pub fn disassemble_reachable(memory: &[i64]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut addr = 0;
+
+    for start in reachable(memory) {
+        lines.extend((addr..start).map(|x| data(x, memory[x])));
+
+        let (line, len) = disassemble_at(memory, start);
+
+        lines.push(line);
+        addr = addr.max(start + len);
+    }
+
+    lines.extend((addr..memory.len()).map(|x| data(x, memory[x])));
+
+    lines
+}
+
+pub fn reachable(memory: &[i64]) -> BTreeSet<usize> {
+    let mut visited = BTreeSet::new();
+    let mut pending = vec![0];
+
+    while let Some(addr) = pending.pop() {
+        if !visited.insert(addr) {
+            continue;
+        }
+
+        let op = match decode(memory, addr) {
+            Some(op) => op,
+            None => continue,
+        };
+
+        for edge in successors(addr, &op) {
+            if let Edge::Taken(x) | Edge::Fallthrough(x) = edge {
+                pending.push(x);
+            }
+        }
+    }
+
+    visited
+        .into_iter()
+        .filter(|x| decode(memory, *x).is_some())
+        .collect()
+}
+
+fn data(addr: usize, value: i64) -> String {
+    format!("{:04}: DATA {}", addr, value)
+}
+
+#[test]
+fn test_disassemble() {
+    let tape = [1101, 5, 3, 100, 22201, 3, -2, 10, 4, 100, 99, 42];
+
+    assert_eq!(
+        disassemble(&tape),
+        vec![
+            "0000: ADD #5, #3 -> [100]",
+            "0004: ADD [rb+3], [rb-2] -> [rb+10]",
+            "0008: OUT [100]",
+            "0010: HLT",
+            "0011: DATA 42",
+        ]
+    );
+}
+
+#[test]
+fn test_disassemble_reachable() {
+    let tape = [1105, 1, 7, 3, 1, 99, 0, 3, 6, 99];
+
+    assert_eq!(
+        disassemble_reachable(&tape),
+        vec![
+            "0000: JNZ #1, #7",
+            "0003: DATA 3",
+            "0004: DATA 1",
+            "0005: DATA 99",
+            "0006: DATA 0",
+            "0007: IN -> [6]",
+            "0009: HLT",
+        ]
+    );
+
+    assert_eq!(
+        disassemble(&tape)[..3],
+        ["0000: JNZ #1, #7", "0003: IN -> [1]", "0005: HLT"]
+    );
+}
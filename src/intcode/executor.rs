@@ -0,0 +1,119 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+type Ready = Arc<Mutex<VecDeque<usize>>>;
+
+#[derive(Default)]
+pub struct Executor {
+    tasks: Vec<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+    ready: Ready,
+}
+
+pub struct Task<T> {
+    result: Rc<RefCell<Option<T>>>,
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Ready,
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.id);
+    }
+}
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+impl<T> Task<T> {
+    pub fn is_finished(&self) -> bool {
+        self.result.borrow().is_some()
+    }
+
+    pub fn take(&self) -> Option<T> {
+        self.result.borrow_mut().take()
+    }
+}
+
+impl Executor {
+    pub fn new() -> Executor {
+        Executor::default()
+    }
+
+    pub fn spawn<F>(&mut self, future: F) -> Task<F::Output>
+    where
+        F: Future + 'static,
+    {
+        let result = Rc::new(RefCell::new(None));
+        let slot = result.clone();
+
+        self.tasks.push(Some(Box::pin(async move {
+            let output = future.await;
+            *slot.borrow_mut() = Some(output);
+        })));
+
+        self.ready.lock().unwrap().push_back(self.tasks.len() - 1);
+
+        Task { result }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.tasks.iter().filter(|x| x.is_some()).count()
+    }
+
+    pub fn run(&mut self) -> usize {
+        loop {
+            let id = match self.ready.lock().unwrap().pop_front() {
+                Some(id) => id,
+                None => break,
+            };
+
+            let task = match self.tasks[id].as_mut() {
+                Some(task) => task,
+                None => continue,
+            };
+
+            let waker = Waker::from(Arc::new(TaskWaker {
+                id,
+                ready: self.ready.clone(),
+            }));
+
+            if task
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks[id] = None;
+            }
+        }
+
+        self.pending()
+    }
+}
+
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        thread::park();
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, Sender, SyncSender};
+
+pub trait IntcodeInput {
+    fn read(&mut self) -> Option<i64>;
+}
+
+pub trait IntcodeOutput {
+    fn write(&mut self, value: i64);
+
+    fn queued(&self) -> usize {
+        0
+    }
+
+    /// Takes back the oldest queued value, if this output keeps a queue.
+    fn pop(&mut self) -> Option<i64> {
+        None
+    }
+}
+
+pub struct IterInput<I>(pub I);
+
+/// Non-blocking channel input: an empty channel makes the computer return
+/// `State::AwaitingInput` instead of waiting for the sender.
+pub struct TryRecvInput(pub Receiver<i64>);
+
+impl IntcodeInput for VecDeque<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+}
+
+impl<I> IntcodeInput for IterInput<I>
+where
+    I: Iterator<Item = i64>,
+{
+    fn read(&mut self) -> Option<i64> {
+        self.0.next()
+    }
+}
+
+impl<F> IntcodeInput for F
+where
+    F: FnMut() -> Option<i64>,
+{
+    fn read(&mut self) -> Option<i64> {
+        self()
+    }
+}
+
+/// Blocks until a value arrives, so the sender must run on another thread.
+/// Use `TryRecvInput` to wire machines together on a single thread.
+impl IntcodeInput for Receiver<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.recv().ok()
+    }
+}
+
+impl IntcodeInput for TryRecvInput {
+    fn read(&mut self) -> Option<i64> {
+        self.0.try_recv().ok()
+    }
+}
+
+impl IntcodeOutput for VecDeque<i64> {
+    fn write(&mut self, value: i64) {
+        self.push_back(value);
+    }
+
+    fn queued(&self) -> usize {
+        self.len()
+    }
+
+    fn pop(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+}
+
+impl IntcodeOutput for Vec<i64> {
+    fn write(&mut self, value: i64) {
+        self.push(value);
+    }
+
+    fn queued(&self) -> usize {
+        self.len()
+    }
+
+    fn pop(&mut self) -> Option<i64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.remove(0))
+        }
+    }
+}
+
+impl<F> IntcodeOutput for F
+where
+    F: FnMut(i64),
+{
+    fn write(&mut self, value: i64) {
+        self(value)
+    }
+}
+
+impl IntcodeOutput for Sender<i64> {
+    fn write(&mut self, value: i64) {
+        let _ = self.send(value);
+    }
+}
+
+impl IntcodeOutput for SyncSender<i64> {
+    fn write(&mut self, value: i64) {
+        let _ = self.send(value);
+    }
+}
@@ -0,0 +1,1262 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeBounds;
+
+mod ascii;
+mod asm;
+mod async_io;
+mod cfg;
+mod circuit;
+mod disasm;
+mod executor;
+mod io;
+mod limits;
+mod memory;
+mod network;
+mod op;
+mod outputs;
+mod overflow;
+mod pipeline;
+mod profile;
+mod smc;
+mod snapshot;
+mod symbolic;
+mod tape;
+mod trace;
+mod transpile;
+mod undo;
+mod watch;
+
+pub use ascii::AsciiError;
+pub use asm::{assemble, AsmError};
+pub use async_io::{async_channel, AsyncReceiver, AsyncSender, RecvFuture, SendFuture};
+pub use cfg::{BasicBlock, Cfg, Edge};
+pub use circuit::{Circuit, CircuitError};
+pub use disasm::{disassemble, disassemble_at, disassemble_reachable};
+pub use executor::{block_on, Executor, Task};
+pub use io::{IntcodeInput, IntcodeOutput, IterInput, TryRecvInput};
+pub use limits::{Limit, ResourceLimits};
+pub use memory::{Memory, MemoryKind};
+pub use network::{Network, NetworkError, Packet, NAT};
+pub use op::{decode, Op, Opcode, Operand};
+pub use outputs::{Outputs, Pairs, Triples};
+pub use overflow::Overflow;
+pub use pipeline::{spawn_connected, MachineHandle};
+pub use profile::Profiler;
+pub use smc::{Modification, SmcDetector};
+pub use snapshot::SnapshotError;
+pub use symbolic::{Expr, Polynomial, Stop, SymbolicComputer};
+pub use tape::{parse_tape, TapeError};
+pub use trace::Tracer;
+pub use transpile::transpile;
+pub use watch::{Access, WatchAction, WatchEvent};
+
+use trace::{Record, TraceSlot};
+use undo::{Entry, History};
+use watch::WatchSlot;
+
+const CACHE_SIZE: usize = 1 << 20;
+
+use op::{parameter_mode, ModeError, MODE_IMMEDIATE, MODE_RELATIVE};
+
+#[derive(Clone)]
+pub struct Computer<I = VecDeque<i64>, O = VecDeque<i64>> {
+    memory: Memory,
+    ip: usize,
+    rbp: i64,
+    is_running: bool,
+    input: I,
+    output: O,
+    tracer: TraceSlot,
+    profiler: Option<Profiler>,
+    smc: Option<SmcDetector>,
+    watches: WatchSlot,
+    paused: bool,
+    history: Option<History>,
+    cache: Vec<Option<Decoded>>,
+    limits: Option<ResourceLimits>,
+    overflow: Overflow,
+    steps: u64,
+    input_waits: u64,
+    partial_chunk: VecDeque<i64>,
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Add { src1: i64, src2: i64, dst: i64 },
+    Mul { src1: i64, src2: i64, dst: i64 },
+    Write { dst: i64 },
+    Read { src: i64 },
+    JumpNotZero { cond: i64, dst: i64 },
+    JumpZero { cond: i64, dst: i64 },
+    LessThan { src1: i64, src2: i64, dst: i64 },
+    Equals { src1: i64, src2: i64, dst: i64 },
+    AdjustRbp { src: i64 },
+    Halt,
+}
+
+impl Instruction {
+    pub fn operands(&self) -> Vec<i64> {
+        match *self {
+            Instruction::Add { src1, src2, dst }
+            | Instruction::Mul { src1, src2, dst }
+            | Instruction::LessThan { src1, src2, dst }
+            | Instruction::Equals { src1, src2, dst } => vec![src1, src2, dst],
+            Instruction::Write { dst } => vec![dst],
+            Instruction::Read { src } | Instruction::AdjustRbp { src } => vec![src],
+            Instruction::JumpNotZero { cond, dst } | Instruction::JumpZero { cond, dst } => {
+                vec![cond, dst]
+            }
+            Instruction::Halt => vec![],
+        }
+    }
+
+    pub fn dst(&self) -> Option<i64> {
+        match *self {
+            Instruction::Add { dst, .. }
+            | Instruction::Mul { dst, .. }
+            | Instruction::LessThan { dst, .. }
+            | Instruction::Equals { dst, .. }
+            | Instruction::Write { dst } => Some(dst),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Decoded {
+    opcode: Opcode,
+    modes: [u8; 3],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Halted,
+    AwaitingInput,
+    Output(i64),
+    Paused,
+    LimitExceeded(Limit),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    IllegalOpcode,
+    IllegalParameterMode(i64),
+    ImmediateDestination,
+    NegativeAddress(i64),
+    EmptyInput,
+    Overflow(Opcode, i64, i64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntcodeError {
+    pub kind: ErrorKind,
+    pub ip: usize,
+    pub intcode: i64,
+    pub opcode: i64,
+    pub parameter: Option<usize>,
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::IllegalOpcode => write!(f, "illegal opcode {}", self.opcode)?,
+            ErrorKind::IllegalParameterMode(mode) => write!(f, "illegal parameter mode {}", mode)?,
+            ErrorKind::ImmediateDestination => write!(f, "dst operand cannot use immediate mode")?,
+            ErrorKind::NegativeAddress(addr) => write!(f, "negative address {}", addr)?,
+            ErrorKind::EmptyInput => write!(f, "input queue is empty")?,
+            ErrorKind::Overflow(opcode, a, b) => {
+                write!(f, "{} overflow with operands {} and {}", opcode, a, b)?
+            }
+        }
+
+        write!(f, " at ip {} (intcode {}", self.ip, self.intcode)?;
+
+        if let Some(parameter) = self.parameter {
+            write!(f, ", parameter {}", parameter)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl Error for IntcodeError {}
+
+impl Computer {
+    pub fn new<M: Into<Memory>>(memory: M) -> Computer {
+        Computer::with_io(memory, VecDeque::new(), VecDeque::new())
+    }
+
+    pub fn from_tape(tape: &str) -> Computer {
+        Computer::try_from_tape(tape).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn push_input(&mut self, input: i64) {
+        self.input.push_back(input);
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop_front()
+    }
+}
+
+impl<I, O> Computer<I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    pub fn with_io<M: Into<Memory>>(memory: M, input: I, output: O) -> Computer<I, O> {
+        Computer {
+            memory: memory.into(),
+            ip: 0,
+            rbp: 0,
+            is_running: true,
+            input,
+            output,
+            tracer: TraceSlot::default(),
+            profiler: None,
+            smc: None,
+            watches: WatchSlot::default(),
+            paused: false,
+            history: None,
+            cache: Vec::new(),
+            limits: None,
+            overflow: Overflow::default(),
+            steps: 0,
+            input_waits: 0,
+            partial_chunk: VecDeque::new(),
+        }
+    }
+
+    pub fn trace(&mut self, tracer: Tracer) {
+        self.tracer.0 = Some(tracer);
+    }
+
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.tracer.0.take()
+    }
+
+    pub fn profile(&mut self, profiler: Profiler) {
+        self.profiler = Some(profiler);
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    pub fn take_profiler(&mut self) -> Option<Profiler> {
+        self.profiler.take()
+    }
+
+    pub fn detect_smc(&mut self, detector: SmcDetector) {
+        self.smc = Some(detector);
+    }
+
+    pub fn smc_detector(&self) -> Option<&SmcDetector> {
+        self.smc.as_ref()
+    }
+
+    pub fn take_smc_detector(&mut self) -> Option<SmcDetector> {
+        self.smc.take()
+    }
+
+    pub fn watch<R, F>(&mut self, addrs: R, access: Access, callback: F) -> usize
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&WatchEvent) -> WatchAction + Send + 'static,
+    {
+        self.watches.add(addrs, access, callback)
+    }
+
+    pub fn unwatch(&mut self, id: usize) -> bool {
+        self.watches.remove(id)
+    }
+
+    pub fn limit(&mut self, limits: ResourceLimits) {
+        self.limits = Some(limits);
+    }
+
+    pub fn limits(&self) -> ResourceLimits {
+        self.limits.unwrap_or_default()
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn input_waits(&self) -> u64 {
+        self.input_waits
+    }
+
+    pub fn input(&self) -> &I {
+        &self.input
+    }
+
+    pub fn input_mut(&mut self) -> &mut I {
+        &mut self.input
+    }
+
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    pub fn output_mut(&mut self) -> &mut O {
+        &mut self.output
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    pub fn get(&self, index: usize) -> Option<i64> {
+        if index < self.memory.len() {
+            Some(self.memory.get(index))
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, index: usize, val: i64) {
+        self.store(index, val);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn rbp(&self) -> i64 {
+        self.rbp
+    }
+
+    pub fn set_ip(&mut self, ip: usize) {
+        self.ip = ip;
+    }
+
+    pub fn set_rbp(&mut self, rbp: i64) {
+        self.rbp = rbp;
+    }
+
+    pub fn compute(&mut self) -> State {
+        self.try_compute().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn compute_until_output(&mut self) -> State {
+        self.try_compute_until_output()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_compute(&mut self) -> Result<State, IntcodeError> {
+        self.run(false)
+    }
+
+    pub fn try_compute_until_output(&mut self) -> Result<State, IntcodeError> {
+        self.run(true)
+    }
+
+    fn run(&mut self, stop_on_output: bool) -> Result<State, IntcodeError> {
+        while self.is_running {
+            if self.paused {
+                self.paused = false;
+                return Ok(State::Paused);
+            }
+
+            if let Some(limit) = self.limits.and_then(|x| x.check_steps(self.steps)) {
+                return Ok(State::LimitExceeded(limit));
+            }
+
+            let instruction = self.fetch_instruction()?;
+
+            if let Some(limit) = self
+                .limits
+                .and_then(|x| self.limit_reached(x, &instruction))
+            {
+                return Ok(State::LimitExceeded(limit));
+            }
+
+            match self.cycle(instruction) {
+                Ok(Instruction::Read { src }) if stop_on_output => return Ok(State::Output(src)),
+                Ok(Instruction::Read { src }) => self.write_output(src),
+                Ok(_) => {}
+                Err(IntcodeError {
+                    kind: ErrorKind::EmptyInput,
+                    ..
+                }) => return Ok(self.await_input()),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(State::Halted)
+    }
+
+    fn write_output(&mut self, value: i64) {
+        self.output.write(value);
+
+        if let Some(history) = self.history.as_mut() {
+            history.note_output(value);
+        }
+    }
+
+    fn limit_reached(&self, limits: ResourceLimits, instruction: &Instruction) -> Option<Limit> {
+        match instruction {
+            Instruction::Read { .. } => limits.check_output_len(self.output.queued()),
+            instruction => limits.check_address(instruction.dst()? as usize),
+        }
+    }
+
+    #[cold]
+    fn await_input(&mut self) -> State {
+        if let Some(limit) = self
+            .limits
+            .and_then(|x| x.check_input_waits(self.input_waits))
+        {
+            return State::LimitExceeded(limit);
+        }
+
+        self.input_waits += 1;
+
+        State::AwaitingInput
+    }
+
+    pub fn try_step(&mut self) -> Result<Instruction, IntcodeError> {
+        let instruction = self.fetch_instruction()?;
+        let instruction = self.cycle(instruction)?;
+
+        if let Instruction::Read { src } = instruction {
+            self.write_output(src);
+        }
+
+        Ok(instruction)
+    }
+
+    fn fetch_instruction(&mut self) -> Result<Instruction, IntcodeError> {
+        match self.fetch_and_decode() {
+            Some(instruction) => Ok(instruction),
+            None => Err(self.diagnose()),
+        }
+    }
+
+    fn cycle(&mut self, instruction: Instruction) -> Result<Instruction, IntcodeError> {
+        if self.is_observed() {
+            return self.traced_cycle(instruction);
+        }
+
+        if self.execute(&instruction).is_none() {
+            return Err(self.diagnose());
+        }
+
+        self.steps += 1;
+
+        Ok(instruction)
+    }
+
+    fn is_observed(&self) -> bool {
+        self.tracer.0.is_some()
+            || self.profiler.is_some()
+            || self.smc.is_some()
+            || !self.watches.is_empty()
+            || self.history.is_some()
+    }
+
+    fn traced_cycle(&mut self, instruction: Instruction) -> Result<Instruction, IntcodeError> {
+        let (ip, rbp) = (self.ip, self.rbp);
+
+        let intcode = self.memory.get(ip);
+        let opcode = Opcode::from_i64(intcode % 100).unwrap();
+
+        let traced = match self.tracer.0.as_ref() {
+            Some(tracer) => tracer.wants(ip, opcode),
+            None => false,
+        };
+
+        let watched = !self.watches.is_empty();
+
+        let len = self.memory.len();
+
+        let old = match instruction.dst() {
+            Some(dst) if traced || watched || self.history.is_some() || self.smc.is_some() => {
+                self.get(dst as usize).unwrap_or(0)
+            }
+            _ => 0,
+        };
+
+        let reads = if watched {
+            self.operand_reads()
+        } else {
+            Vec::new()
+        };
+
+        if self.execute(&instruction).is_none() {
+            return Err(self.diagnose());
+        }
+
+        let write = instruction
+            .dst()
+            .map(|dst| (dst as usize, old, self.memory.get(dst as usize)));
+
+        if let Some(tracer) = self.tracer.0.as_mut() {
+            tracer.tick();
+        }
+
+        if traced {
+            let record = Record {
+                ip,
+                rbp,
+                intcode,
+                opcode,
+                operands: instruction.operands(),
+                write,
+                input: match instruction {
+                    Instruction::Write { .. } => write.map(|x| x.2),
+                    _ => None,
+                },
+                output: match instruction {
+                    Instruction::Read { src } => Some(src),
+                    _ => None,
+                },
+            };
+
+            self.tracer.0.as_mut().unwrap().record(&record);
+        }
+
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.record(ip, intcode, &instruction);
+        }
+
+        if let Some(smc) = self.smc.as_mut() {
+            smc.record(ip, opcode.arity() + 1, write);
+        }
+
+        if let Some(history) = self.history.as_mut() {
+            history.push(Entry {
+                ip,
+                rbp,
+                len,
+                write: write.map(|x| (x.0, x.1)),
+                input: match instruction {
+                    Instruction::Write { .. } => write.map(|x| x.2),
+                    _ => None,
+                },
+                output: None,
+            });
+        }
+
+        if watched {
+            let writes = write.map(|(addr, old, new)| (addr, Access::Write, old, new));
+
+            for (addr, access, old, new) in reads.into_iter().chain(writes) {
+                let event = WatchEvent {
+                    ip,
+                    addr,
+                    access,
+                    old,
+                    new,
+                };
+
+                if self.watches.notify(&event) == WatchAction::Pause {
+                    self.paused = true;
+                }
+            }
+        }
+
+        self.steps += 1;
+
+        Ok(instruction)
+    }
+
+    fn operand_reads(&self) -> Vec<(usize, Access, i64, i64)> {
+        let op = match self.memory.decode(self.ip) {
+            Some(op) => op,
+            None => return Vec::new(),
+        };
+
+        let sources = op.operands().len() - op.opcode.has_dst() as usize;
+
+        op.operands()[..sources]
+            .iter()
+            .filter_map(|x| match *x {
+                Operand::Position(addr) => Some(addr),
+                Operand::Relative(offset) => self.overflow.add(self.rbp, offset),
+                Operand::Immediate(_) => None,
+            })
+            .filter(|x| *x >= 0)
+            .map(|x| {
+                let value = self.memory.get(x as usize);
+                (x as usize, Access::Read, value, value)
+            })
+            .collect()
+    }
+
+    #[cold]
+    fn error(&self, kind: ErrorKind, parameter: Option<usize>) -> IntcodeError {
+        let intcode = self.get(self.ip).unwrap_or(0);
+
+        IntcodeError {
+            kind,
+            ip: self.ip,
+            intcode,
+            opcode: intcode % 100,
+            parameter,
+        }
+    }
+
+    #[cold]
+    fn diagnose(&self) -> IntcodeError {
+        let intcode = self.memory.get(self.ip);
+
+        let opcode = match Opcode::from_i64(intcode % 100) {
+            Some(opcode) => opcode,
+            None => return self.error(ErrorKind::IllegalOpcode, None),
+        };
+
+        let mut operands = [0; 3];
+
+        for (parameter, operand) in operands.iter_mut().enumerate().take(opcode.arity()) {
+            let value = self.memory.get(self.ip + parameter + 1);
+
+            let addr = match parameter_mode(intcode, opcode, parameter) {
+                Ok(MODE_IMMEDIATE) => {
+                    *operand = value;
+                    continue;
+                }
+                Ok(MODE_RELATIVE) => match self.overflow.add(self.rbp, value) {
+                    Some(addr) => addr,
+                    None => {
+                        return self.error(
+                            ErrorKind::Overflow(opcode, self.rbp, value),
+                            Some(parameter),
+                        )
+                    }
+                },
+                Ok(_) => value,
+                Err(ModeError::ImmediateDestination) => {
+                    return self.error(ErrorKind::ImmediateDestination, Some(parameter))
+                }
+                Err(ModeError::Illegal(mode)) => {
+                    return self.error(ErrorKind::IllegalParameterMode(mode), Some(parameter))
+                }
+            };
+
+            if addr < 0 {
+                return self.error(ErrorKind::NegativeAddress(addr), Some(parameter));
+            }
+
+            *operand = self.memory.get(addr as usize);
+        }
+
+        match opcode {
+            Opcode::Add | Opcode::Mul => {
+                self.error(ErrorKind::Overflow(opcode, operands[0], operands[1]), None)
+            }
+            Opcode::AdjustRbp => {
+                self.error(ErrorKind::Overflow(opcode, self.rbp, operands[0]), None)
+            }
+            Opcode::Write => self.error(ErrorKind::EmptyInput, None),
+            Opcode::JumpNotZero | Opcode::JumpZero => {
+                self.error(ErrorKind::NegativeAddress(operands[1]), Some(1))
+            }
+            _ => unreachable!("instruction at ip {} cannot fail", self.ip),
+        }
+    }
+
+    fn store(&mut self, addr: usize, val: i64) {
+        self.memory.set(addr, val);
+
+        if let Some(decoded) = self.cache.get_mut(addr) {
+            *decoded = None;
+        }
+    }
+
+    fn fetch_operand(&self, modes: [u8; 3], parameter: usize) -> Option<i64> {
+        let value = self.memory.get(self.ip + parameter + 1);
+
+        let addr = match modes[parameter] {
+            MODE_IMMEDIATE => return Some(value),
+            MODE_RELATIVE => self.overflow.add(self.rbp, value)?,
+            _ => value,
+        };
+
+        if addr < 0 {
+            return None;
+        }
+
+        Some(self.memory.get(addr as usize))
+    }
+
+    fn fetch_dst_address(&self, modes: [u8; 3], parameter: usize) -> Option<i64> {
+        let value = self.memory.get(self.ip + parameter + 1);
+
+        let addr = match modes[parameter] {
+            MODE_RELATIVE => self.overflow.add(self.rbp, value)?,
+            _ => value,
+        };
+
+        if addr < 0 {
+            return None;
+        }
+
+        Some(addr)
+    }
+
+    fn decode(&self) -> Option<Decoded> {
+        let intcode = self.memory.get(self.ip);
+        let opcode = Opcode::from_i64(intcode % 100)?;
+
+        let mut modes = [0; 3];
+
+        for (parameter, mode) in modes.iter_mut().enumerate().take(opcode.arity()) {
+            *mode = parameter_mode(intcode, opcode, parameter).ok()?;
+        }
+
+        Some(Decoded { opcode, modes })
+    }
+
+    fn fetch(&mut self) -> Option<Decoded> {
+        if let Some(Some(decoded)) = self.cache.get(self.ip) {
+            return Some(*decoded);
+        }
+
+        let decoded = self.decode()?;
+
+        if self.ip < CACHE_SIZE {
+            if self.ip >= self.cache.len() {
+                self.cache.resize(self.ip + 1, None);
+            }
+
+            self.cache[self.ip] = Some(decoded);
+        }
+
+        Some(decoded)
+    }
+
+    fn fetch_and_decode(&mut self) -> Option<Instruction> {
+        let Decoded { opcode, modes } = self.fetch()?;
+
+        let instruction = match opcode {
+            Opcode::Add => Instruction::Add {
+                src1: self.fetch_operand(modes, 0)?,
+                src2: self.fetch_operand(modes, 1)?,
+                dst: self.fetch_dst_address(modes, 2)?,
+            },
+            Opcode::Mul => Instruction::Mul {
+                src1: self.fetch_operand(modes, 0)?,
+                src2: self.fetch_operand(modes, 1)?,
+                dst: self.fetch_dst_address(modes, 2)?,
+            },
+            Opcode::Write => Instruction::Write {
+                dst: self.fetch_dst_address(modes, 0)?,
+            },
+            Opcode::Read => Instruction::Read {
+                src: self.fetch_operand(modes, 0)?,
+            },
+            Opcode::JumpNotZero => Instruction::JumpNotZero {
+                cond: self.fetch_operand(modes, 0)?,
+                dst: self.fetch_operand(modes, 1)?,
+            },
+            Opcode::JumpZero => Instruction::JumpZero {
+                cond: self.fetch_operand(modes, 0)?,
+                dst: self.fetch_operand(modes, 1)?,
+            },
+            Opcode::LessThan => Instruction::LessThan {
+                src1: self.fetch_operand(modes, 0)?,
+                src2: self.fetch_operand(modes, 1)?,
+                dst: self.fetch_dst_address(modes, 2)?,
+            },
+            Opcode::Equals => Instruction::Equals {
+                src1: self.fetch_operand(modes, 0)?,
+                src2: self.fetch_operand(modes, 1)?,
+                dst: self.fetch_dst_address(modes, 2)?,
+            },
+            Opcode::AdjustRbp => Instruction::AdjustRbp {
+                src: self.fetch_operand(modes, 0)?,
+            },
+            Opcode::Halt => Instruction::Halt,
+        };
+
+        Some(instruction)
+    }
+
+    fn execute(&mut self, instruction: &Instruction) -> Option<()> {
+        match *instruction {
+            Instruction::Add { src1, src2, dst } => {
+                self.store(dst as usize, self.overflow.add(src1, src2)?);
+                self.ip += 4;
+            }
+            Instruction::Mul { src1, src2, dst } => {
+                self.store(dst as usize, self.overflow.mul(src1, src2)?);
+                self.ip += 4;
+            }
+            Instruction::Write { dst } => {
+                let src = self.input.read()?;
+                self.store(dst as usize, src);
+                self.ip += 2;
+            }
+            Instruction::Read { .. } => {
+                self.ip += 2;
+            }
+            Instruction::JumpNotZero { cond, dst } => {
+                if cond != 0 {
+                    self.jump(dst)?;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            Instruction::JumpZero { cond, dst } => {
+                if cond == 0 {
+                    self.jump(dst)?;
+                } else {
+                    self.ip += 3;
+                }
+            }
+            Instruction::LessThan { src1, src2, dst } => {
+                self.store(dst as usize, (src1 < src2) as i64);
+                self.ip += 4;
+            }
+            Instruction::Equals { src1, src2, dst } => {
+                self.store(dst as usize, (src1 == src2) as i64);
+                self.ip += 4;
+            }
+            Instruction::AdjustRbp { src } => {
+                self.rbp = self.overflow.add(self.rbp, src)?;
+                self.ip += 2;
+            }
+            Instruction::Halt => {
+                self.is_running = false;
+            }
+        }
+
+        Some(())
+    }
+
+    fn jump(&mut self, dst: i64) -> Option<()> {
+        if dst < 0 {
+            return None;
+        }
+
+        self.ip = dst as usize;
+
+        Some(())
+    }
+}
+
+#[test]
+fn test_position_equals() {
+    let mut computer = Computer::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]);
+    computer.push_input(8);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(1));
+    assert_eq!(computer.pop_output(), None);
+
+    let mut computer = Computer::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]);
+    computer.push_input(9);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(0));
+    assert_eq!(computer.pop_output(), None);
+}
+
+#[test]
+fn test_position_less_than() {
+    let mut computer = Computer::new(vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8]);
+    computer.push_input(7);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(1));
+    assert_eq!(computer.pop_output(), None);
+
+    let mut computer = Computer::new(vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8]);
+    computer.push_input(8);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(0));
+    assert_eq!(computer.pop_output(), None);
+}
+
+#[test]
+fn test_immediate_equals() {
+    let mut computer = Computer::new(vec![3, 3, 1108, -1, 8, 3, 4, 3, 99]);
+    computer.push_input(8);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(1));
+    assert_eq!(computer.pop_output(), None);
+
+    let mut computer = Computer::new(vec![3, 3, 1108, -1, 8, 3, 4, 3, 99]);
+    computer.push_input(9);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(0));
+    assert_eq!(computer.pop_output(), None);
+}
+
+#[test]
+fn test_immediate_less_than() {
+    let mut computer = Computer::new(vec![3, 3, 1107, -1, 8, 3, 4, 3, 99]);
+    computer.push_input(7);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(1));
+    assert_eq!(computer.pop_output(), None);
+
+    let mut computer = Computer::new(vec![3, 3, 1107, -1, 8, 3, 4, 3, 99]);
+    computer.push_input(8);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(0));
+    assert_eq!(computer.pop_output(), None);
+}
+
+#[test]
+fn test_illegal_opcode() {
+    let mut computer = Computer::new(vec![1101, 1, 2, 5, 42, 0]);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::IllegalOpcode);
+    assert_eq!(error.ip, 4);
+    assert_eq!(error.intcode, 42);
+    assert_eq!(error.opcode, 42);
+    assert_eq!(error.parameter, None);
+}
+
+#[test]
+fn test_illegal_parameter_mode() {
+    let mut computer = Computer::new(vec![3001, 1, 2, 0, 99]);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::IllegalParameterMode(3));
+    assert_eq!(error.opcode, 1);
+    assert_eq!(error.parameter, Some(1));
+
+    let mut computer = Computer::new(vec![11101, 1, 2, 0, 99]);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::ImmediateDestination);
+    assert_eq!(error.parameter, Some(2));
+}
+
+#[test]
+fn test_negative_address() {
+    let mut computer = Computer::new(vec![1, -1, 0, 0, 99]);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NegativeAddress(-1));
+    assert_eq!(error.parameter, Some(0));
+
+    let mut computer = Computer::new(vec![109, -5, 204, 1, 99]);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NegativeAddress(-4));
+    assert_eq!(error.ip, 2);
+}
+
+#[test]
+fn test_overflow_policy() {
+    let tape = vec![1102, i64::MAX, 2, 9, 109, i64::MAX, 109, 1, 99, 0];
+
+    let mut computer = Computer::new(tape.clone());
+    assert_eq!(computer.overflow(), Overflow::Wrapping);
+    computer.compute();
+    assert_eq!(computer.get(9), Some(-2));
+    assert_eq!(computer.rbp(), i64::MIN);
+
+    let mut computer = Computer::new(tape.clone());
+    computer.set_overflow(Overflow::Saturating);
+    computer.compute();
+    assert_eq!(computer.get(9), Some(i64::MAX));
+    assert_eq!(computer.rbp(), i64::MAX);
+
+    let mut computer = Computer::new(tape);
+    computer.set_overflow(Overflow::Checked);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Overflow(Opcode::Mul, i64::MAX, 2));
+    assert_eq!(error.ip, 0);
+
+    computer.set_ip(4);
+    computer.set_rbp(1);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(
+        error.kind,
+        ErrorKind::Overflow(Opcode::AdjustRbp, 1, i64::MAX)
+    );
+    assert_eq!(
+        error.to_string(),
+        "ARB overflow with operands 1 and 9223372036854775807 at ip 4 (intcode 109)"
+    );
+
+    let tape = vec![109, i64::MAX, 204, 1, 99];
+
+    let mut computer = Computer::new(tape.clone());
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NegativeAddress(i64::MIN));
+    assert_eq!((error.ip, error.parameter), (2, Some(0)));
+
+    let mut computer = Computer::new(tape.clone());
+    computer.set_overflow(Overflow::Saturating);
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.pop_output(), Some(0));
+
+    let mut computer = Computer::new(tape);
+    computer.set_overflow(Overflow::Checked);
+    let error = computer.try_compute().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Overflow(Opcode::Read, i64::MAX, 1));
+    assert_eq!((error.ip, error.parameter), (2, Some(0)));
+}
+
+#[test]
+fn test_empty_input_is_recoverable() {
+    let mut computer = Computer::new(vec![3, 0, 4, 0, 99]);
+    let error = computer.try_step().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::EmptyInput);
+    assert_eq!(error.ip, 0);
+
+    computer.push_input(7);
+    assert_eq!(computer.try_compute(), Ok(State::Halted));
+    assert_eq!(computer.pop_output(), Some(7));
+}
+
+#[test]
+fn test_run_states() {
+    let mut computer = Computer::new(vec![3, 11, 4, 11, 4, 11, 3, 11, 4, 11, 99, 0]);
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    assert!(computer.is_running());
+
+    computer.push_input(5);
+    assert_eq!(computer.compute_until_output(), State::Output(5));
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    assert_eq!(computer.pop_output(), Some(5));
+    assert_eq!(computer.pop_output(), None);
+
+    computer.push_input(6);
+    assert_eq!(computer.compute_until_output(), State::Output(6));
+    assert_eq!(computer.compute_until_output(), State::Halted);
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.pop_output(), None);
+}
+
+#[test]
+fn test_pluggable_io() {
+    let mut out = Vec::new();
+    let mut computer = Computer::with_io(
+        vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0],
+        IterInput(vec![41].into_iter()),
+        |x| out.push(x),
+    );
+    assert_eq!(computer.compute(), State::Halted);
+    drop(computer);
+    assert_eq!(out, vec![42]);
+
+    let (input_tx, input_rx) = std::sync::mpsc::channel();
+    let (output_tx, output_rx) = std::sync::mpsc::channel();
+    let mut computer = Computer::with_io(vec![3, 0, 4, 0, 3, 0, 4, 0, 99], input_rx, output_tx);
+    input_tx.send(1).unwrap();
+    input_tx.send(2).unwrap();
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(output_rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+    let (a_tx, a_rx) = std::sync::mpsc::channel();
+    let (b_tx, b_rx) = std::sync::mpsc::channel();
+    let mut a = Computer::with_io(vec![3, 0, 4, 0, 99], TryRecvInput(a_rx), b_tx);
+    let mut b = Computer::with_io(
+        vec![3, 0, 1001, 0, 1, 0, 4, 0, 99],
+        TryRecvInput(b_rx),
+        a_tx.clone(),
+    );
+    assert_eq!(b.compute(), State::AwaitingInput);
+    a_tx.send(1).unwrap();
+    assert_eq!(a.compute(), State::Halted);
+    assert_eq!(b.compute(), State::Halted);
+    assert_eq!(a.input_mut().0.try_recv(), Ok(2));
+
+    let mut next = 10;
+    let mut computer = Computer::with_io(
+        vec![3, 0, 4, 0, 3, 0, 4, 0, 99],
+        || {
+            next += 1;
+            Some(next)
+        },
+        Vec::new(),
+    );
+    assert_eq!(computer.compute_until_output(), State::Output(11));
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.output(), &vec![12]);
+}
+
+#[test]
+fn test_trace() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buffer = Buffer::default();
+    let mut computer = Computer::new(vec![3, 9, 1002, 9, 3, 9, 4, 9, 99, 0]);
+    computer.trace(Tracer::new(buffer.clone()));
+    computer.push_input(5);
+    computer.compute();
+
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        trace.lines().collect::<Vec<&str>>(),
+        vec![
+            r#"{"step":1,"ip":0,"rbp":0,"intcode":3,"opcode":3,"mnemonic":"IN","modes":[0],"operands":[9],"writes":[{"addr":9,"old":0,"new":5}],"input":5}"#,
+            r#"{"step":2,"ip":2,"rbp":0,"intcode":1002,"opcode":2,"mnemonic":"MUL","modes":[0,1,0],"operands":[5,3,9],"writes":[{"addr":9,"old":5,"new":15}]}"#,
+            r#"{"step":3,"ip":6,"rbp":0,"intcode":4,"opcode":4,"mnemonic":"OUT","modes":[0],"operands":[15],"writes":[],"output":15}"#,
+            r#"{"step":4,"ip":8,"rbp":0,"intcode":99,"opcode":99,"mnemonic":"HLT","modes":[],"operands":[],"writes":[]}"#,
+        ]
+    );
+
+    let buffer = Buffer::default();
+    let mut computer = Computer::new(vec![3, 9, 1002, 9, 3, 9, 4, 9, 99, 0]);
+    computer.trace(
+        Tracer::new(buffer.clone())
+            .ip_range(1..8)
+            .opcodes(&[Opcode::Read]),
+    );
+    computer.push_input(5);
+    computer.compute();
+
+    assert_eq!(computer.take_tracer().unwrap().steps(), 4);
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(trace.lines().count(), 1);
+    assert!(trace.starts_with(r#"{"step":3,"ip":6,"#));
+
+    let buffer = Buffer::default();
+    let mut computer = Computer::new(vec![3, 5, 4, 5, 99, 0]);
+    computer.trace(Tracer::new(buffer.clone()));
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    computer.push_input(5);
+    computer.compute();
+
+    assert_eq!(computer.take_tracer().unwrap().steps(), computer.steps());
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert!(trace.starts_with(r#"{"step":1,"ip":0,"#));
+    assert!(trace
+        .lines()
+        .last()
+        .unwrap()
+        .starts_with(r#"{"step":3,"ip":4,"#));
+}
+
+#[test]
+fn test_huge_addresses() {
+    let mut computer = Computer::new(vec![
+        21101,
+        7,
+        0,
+        1_000_000_000_000,
+        204,
+        1_000_000_000_000,
+        99,
+    ]);
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.pop_output(), Some(7));
+    assert_eq!(computer.memory().kind(), MemoryKind::Paged);
+
+    let mut computer = Computer::new(Memory::dense(vec![1101, 1, 2, 10, 4, 10, 99]));
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.pop_output(), Some(3));
+    assert_eq!(computer.memory().len(), 11);
+}
+
+#[test]
+fn test_self_modifying_code() {
+    let tape = assemble(
+        "
+        loop:   ADD [patch+1], #1 -> [patch+1]
+        patch:  ADD #0, #0 -> [acc]
+                OUT [acc]
+                ADD [n], #-1 -> [n]
+                JNZ [n], #loop
+                MUL #1, #1 -> [patch]
+                HLT
+        acc:    .data 0
+        n:      .data 3
+        ",
+    )
+    .unwrap();
+
+    let mut computer = Computer::new(tape.clone());
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(
+        (0..3)
+            .filter_map(|_| computer.pop_output())
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+
+    let mut computer = Computer::new(tape);
+    for _ in 0..3 {
+        computer.try_step().unwrap();
+    }
+    computer.set(4, 1102);
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(
+        (0..3)
+            .filter_map(|_| computer.pop_output())
+            .collect::<Vec<_>>(),
+        vec![1, 0, 0]
+    );
+}
+
+#[test]
+fn test_error_precedence() {
+    let mut computer = Computer::new(vec![3001, -1, 2, 0, 99]);
+    let error = computer.try_step().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::NegativeAddress(-1));
+    assert_eq!(error.parameter, Some(0));
+}
+
+#[test]
+fn test_resource_limits() {
+    let mut computer = Computer::new(vec![1105, 1, 0]);
+    computer.limit(ResourceLimits::new().steps(1000));
+    assert_eq!(computer.compute(), State::LimitExceeded(Limit::Steps(1000)));
+    assert_eq!(computer.steps(), 1000);
+    computer.limit(computer.limits().steps(1500));
+    assert_eq!(computer.compute(), State::LimitExceeded(Limit::Steps(1500)));
+
+    let mut computer = Computer::new(vec![21101, 7, 0, 1_000_000_000_000, 99]);
+    computer.limit(ResourceLimits::new().max_address(1 << 20));
+    assert_eq!(
+        computer.compute(),
+        State::LimitExceeded(Limit::Address(1 << 20))
+    );
+    assert_eq!((computer.ip(), computer.memory().len()), (0, 5));
+    computer.limit(ResourceLimits::new());
+    assert_eq!(computer.compute(), State::Halted);
+
+    let mut computer = Computer::new(vec![104, 1, 1105, 1, 0]);
+    computer.limit(ResourceLimits::new().output_len(3));
+    assert_eq!(
+        computer.compute(),
+        State::LimitExceeded(Limit::OutputLen(3))
+    );
+    computer.pop_output();
+    assert_eq!(
+        computer.compute(),
+        State::LimitExceeded(Limit::OutputLen(3))
+    );
+    assert_eq!(computer.steps(), 8);
+
+    let mut computer = Computer::new(vec![3, 5, 1105, 1, 0, 0]);
+    computer.limit(ResourceLimits::new().input_waits(2));
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    computer.push_input(1);
+    assert_eq!(computer.compute(), State::AwaitingInput);
+    assert_eq!(
+        computer.compute(),
+        State::LimitExceeded(Limit::InputWaits(2))
+    );
+    assert_eq!(computer.input_waits(), 2);
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    steps: Option<u64>,
+    max_address: Option<usize>,
+    output_len: Option<usize>,
+    input_waits: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    Steps(u64),
+    Address(usize),
+    OutputLen(usize),
+    InputWaits(u64),
+}
+
+impl ResourceLimits {
+    pub fn new() -> ResourceLimits {
+        ResourceLimits::default()
+    }
+
+    pub fn steps(mut self, steps: u64) -> ResourceLimits {
+        self.steps = Some(steps);
+        self
+    }
+
+    pub fn max_address(mut self, addr: usize) -> ResourceLimits {
+        self.max_address = Some(addr);
+        self
+    }
+
+    pub fn output_len(mut self, len: usize) -> ResourceLimits {
+        self.output_len = Some(len);
+        self
+    }
+
+    pub fn input_waits(mut self, waits: u64) -> ResourceLimits {
+        self.input_waits = Some(waits);
+        self
+    }
+
+    pub(crate) fn check_steps(&self, steps: u64) -> Option<Limit> {
+        self.steps.filter(|x| steps >= *x).map(Limit::Steps)
+    }
+
+    pub(crate) fn check_address(&self, addr: usize) -> Option<Limit> {
+        self.max_address.filter(|x| addr > *x).map(Limit::Address)
+    }
+
+    pub(crate) fn check_output_len(&self, len: usize) -> Option<Limit> {
+        self.output_len.filter(|x| len >= *x).map(Limit::OutputLen)
+    }
+
+    pub(crate) fn check_input_waits(&self, waits: u64) -> Option<Limit> {
+        self.input_waits
+            .filter(|x| waits >= *x)
+            .map(Limit::InputWaits)
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Steps(x) => write!(f, "instruction limit of {} reached", x),
+            Limit::Address(x) => write!(f, "address limit of {} reached", x),
+            Limit::OutputLen(x) => write!(f, "output queue limit of {} reached", x),
+            Limit::InputWaits(x) => write!(f, "input wait limit of {} reached", x),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use crate::disasm::disassemble_words;
+use crate::op::{decode, Op};
+
+const PAGE_BITS: usize = 10;
+const PAGE_SIZE: usize = 1 << PAGE_BITS;
+const DIRECTORY_PAGES: usize = 1 << 16;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MemoryKind {
+    Dense,
+    #[default]
+    Paged,
+}
+
+#[derive(Clone)]
+pub struct Memory {
+    backend: Backend,
+    len: usize,
+}
+
+#[derive(Clone)]
+enum Backend {
+    Dense(Vec<i64>),
+    Paged {
+        directory: Vec<Option<Box<[i64]>>>,
+        overflow: HashMap<usize, Box<[i64]>>,
+    },
+}
+
+impl Memory {
+    pub fn new(kind: MemoryKind, words: Vec<i64>) -> Memory {
+        match kind {
+            MemoryKind::Dense => Memory {
+                len: words.len(),
+                backend: Backend::Dense(words),
+            },
+            MemoryKind::Paged => {
+                let mut memory = Memory {
+                    backend: Backend::Paged {
+                        directory: Vec::new(),
+                        overflow: HashMap::new(),
+                    },
+                    len: 0,
+                };
+
+                memory.load(0, &words);
+
+                memory
+            }
+        }
+    }
+
+    pub fn dense(words: Vec<i64>) -> Memory {
+        Memory::new(MemoryKind::Dense, words)
+    }
+
+    pub fn paged(words: Vec<i64>) -> Memory {
+        Memory::new(MemoryKind::Paged, words)
+    }
+
+    pub fn kind(&self) -> MemoryKind {
+        match self.backend {
+            Backend::Dense(_) => MemoryKind::Dense,
+            Backend::Paged { .. } => MemoryKind::Paged,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, addr: usize) -> i64 {
+        match &self.backend {
+            Backend::Dense(words) => words.get(addr).copied().unwrap_or(0),
+            Backend::Paged {
+                directory,
+                overflow,
+            } => {
+                let page = addr >> PAGE_BITS;
+
+                if page >= DIRECTORY_PAGES {
+                    return overflow_get(overflow, addr);
+                }
+
+                directory
+                    .get(page)
+                    .and_then(|x| x.as_ref())
+                    .map_or(0, |x| x[addr & (PAGE_SIZE - 1)])
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, addr: usize, val: i64) {
+        self.len = self.len.max(addr + 1);
+
+        match &mut self.backend {
+            Backend::Dense(words) => {
+                if addr >= words.len() {
+                    words.resize(addr + 1, 0);
+                }
+                words[addr] = val;
+            }
+            Backend::Paged {
+                directory,
+                overflow,
+            } => {
+                let page = addr >> PAGE_BITS;
+
+                let words = if page < DIRECTORY_PAGES {
+                    if page >= directory.len() {
+                        if val == 0 {
+                            return;
+                        }
+                        directory.resize(page + 1, None);
+                    }
+
+                    if directory[page].is_none() && val == 0 {
+                        return;
+                    }
+
+                    directory[page].get_or_insert_with(new_page)
+                } else {
+                    if !overflow.contains_key(&page) && val == 0 {
+                        return;
+                    }
+
+                    overflow.entry(page).or_insert_with(new_page)
+                };
+
+                words[addr & (PAGE_SIZE - 1)] = val;
+            }
+        }
+    }
+
+    pub fn load(&mut self, addr: usize, words: &[i64]) {
+        for (i, x) in words.iter().enumerate() {
+            self.set(addr + i, *x);
+        }
+
+        self.len = self.len.max(addr + words.len());
+    }
+
+    pub(crate) fn extend_to(&mut self, len: usize) {
+        if let Backend::Dense(words) = &mut self.backend {
+            if len > words.len() {
+                words.resize(len, 0);
+            }
+        }
+
+        self.len = self.len.max(len);
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if let Backend::Dense(words) = &mut self.backend {
+            words.truncate(len);
+        }
+
+        self.len = self.len.min(len);
+    }
+
+    pub fn window(&self, addr: usize, len: usize) -> Vec<i64> {
+        (addr..addr + len).map(|x| self.get(x)).collect()
+    }
+
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.window(0, self.len)
+    }
+
+    pub fn chunks(&self) -> Vec<(usize, &[i64])> {
+        match &self.backend {
+            Backend::Dense(words) => vec![(0, &words[..])],
+            Backend::Paged {
+                directory,
+                overflow,
+            } => {
+                let mut chunks = directory
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, x)| x.as_ref().map(|x| (i << PAGE_BITS, &x[..])))
+                    .filter(|x| x.0 < self.len)
+                    .collect::<Vec<(usize, &[i64])>>();
+
+                let mut high = overflow
+                    .iter()
+                    .map(|(i, x)| (i << PAGE_BITS, &x[..]))
+                    .filter(|x| x.0 < self.len)
+                    .collect::<Vec<(usize, &[i64])>>();
+                high.sort_by_key(|x| x.0);
+
+                chunks.extend(high);
+
+                chunks
+                    .into_iter()
+                    .map(|(addr, words)| (addr, &words[..words.len().min(self.len - addr)]))
+                    .collect()
+            }
+        }
+    }
+
+    pub fn decode(&self, addr: usize) -> Option<Op> {
+        decode(&self.window(addr, 4), 0)
+    }
+
+    pub fn disassemble_at(&self, addr: usize) -> (String, usize) {
+        disassemble_words(&self.window(addr, 4), addr)
+    }
+}
+
+#[cold]
+fn overflow_get(overflow: &HashMap<usize, Box<[i64]>>, addr: usize) -> i64 {
+    overflow
+        .get(&(addr >> PAGE_BITS))
+        .map_or(0, |x| x[addr & (PAGE_SIZE - 1)])
+}
+
+fn new_page() -> Box<[i64]> {
+    vec![0; PAGE_SIZE].into_boxed_slice()
+}
+
+impl From<Vec<i64>> for Memory {
+    fn from(words: Vec<i64>) -> Memory {
+        Memory::new(MemoryKind::default(), words)
+    }
+}
+
+#[test]
+fn test_paged_memory() {
+    let mut memory = Memory::paged(vec![1, 2, 3]);
+    assert_eq!(memory.len(), 3);
+
+    memory.set(1_000_000_000_000, 42);
+    memory.set(5_000, 0);
+    memory.set(PAGE_SIZE - 1, -1);
+
+    assert_eq!(memory.get(1_000_000_000_000), 42);
+    assert_eq!(memory.get(999_999_999_999), 0);
+    assert_eq!(memory.get(PAGE_SIZE - 1), -1);
+    assert_eq!(memory.get(1), 2);
+    assert_eq!(memory.len(), 1_000_000_000_001);
+    assert_eq!(memory.chunks().len(), 2);
+    assert_eq!(
+        memory.chunks()[1].0,
+        1_000_000_000_000 >> PAGE_BITS << PAGE_BITS
+    );
+
+    let mut memory = Memory::dense(vec![1, 2, 3]);
+    memory.set(10, 5);
+    assert_eq!(memory.to_vec(), vec![1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 5]);
+}
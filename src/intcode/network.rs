@@ -0,0 +1,244 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Computer, IntcodeError, Memory};
+
+pub const NAT: usize = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub src: usize,
+    pub dest: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkError {
+    pub machine: usize,
+    pub error: IntcodeError,
+}
+
+#[derive(Clone)]
+pub struct Network {
+    machines: Vec<Computer>,
+    nat: bool,
+    nat_packet: Option<Packet>,
+    idle: bool,
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}: ({}, {})", self.src, self.dest, self.x, self.y)
+    }
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "machine {}: {}", self.machine, self.error)
+    }
+}
+
+impl Error for NetworkError {}
+
+impl Network {
+    pub fn new<M: Into<Memory>>(memory: M, size: usize) -> Network {
+        let computer = Computer::new(memory);
+
+        let machines = (0..size)
+            .map(|addr| {
+                let mut machine = computer.clone();
+                machine.push_input(addr as i64);
+                machine
+            })
+            .collect();
+
+        Network {
+            machines,
+            nat: false,
+            nat_packet: None,
+            idle: false,
+        }
+    }
+
+    pub fn with_nat(mut self) -> Network {
+        self.nat = true;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    pub fn machine(&self, addr: usize) -> &Computer {
+        &self.machines[addr]
+    }
+
+    pub fn nat_packet(&self) -> Option<Packet> {
+        self.nat_packet
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    pub fn round(&mut self) -> Result<Vec<Packet>, NetworkError> {
+        let mut packets = Vec::new();
+        let mut idle = true;
+
+        for src in 0..self.machines.len() {
+            let machine = &mut self.machines[src];
+
+            if !machine.is_running() {
+                continue;
+            }
+
+            if machine.input().is_empty() {
+                machine.push_input(-1);
+            } else {
+                idle = false;
+            }
+
+            if let Err(error) = machine.try_compute() {
+                return Err(NetworkError {
+                    machine: src,
+                    error,
+                });
+            }
+
+            while machine.output().len() >= 3 {
+                let dest = machine.pop_output().unwrap();
+                let x = machine.pop_output().unwrap();
+                let y = machine.pop_output().unwrap();
+
+                packets.push(Packet { src, dest, x, y });
+            }
+        }
+
+        for packet in &packets {
+            self.send(*packet);
+        }
+
+        self.idle = idle && packets.is_empty();
+
+        if self.idle {
+            if let Some(packet) = self.nat_packet {
+                let packet = Packet {
+                    src: NAT,
+                    dest: 0,
+                    ..packet
+                };
+
+                self.send(packet);
+                packets.push(packet);
+            }
+        }
+
+        Ok(packets)
+    }
+
+    pub fn run_until<F>(&mut self, mut f: F) -> Result<Option<Packet>, NetworkError>
+    where
+        F: FnMut(&Packet) -> bool,
+    {
+        loop {
+            let packets = self.round()?;
+
+            if let Some(packet) = packets.iter().find(|x| f(x)) {
+                return Ok(Some(*packet));
+            }
+
+            if packets.is_empty() && self.idle {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn send(&mut self, packet: Packet) {
+        match packet.dest {
+            x if x >= 0 && (x as usize) < self.machines.len() => {
+                let machine = &mut self.machines[x as usize];
+                machine.push_input(packet.x);
+                machine.push_input(packet.y);
+            }
+            x if x == NAT as i64 && self.nat => self.nat_packet = Some(packet),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_network() {
+    let tape = crate::assemble(
+        "
+            IN -> [addr]
+            JNZ [addr], #loop
+            OUT #1
+            OUT #10
+            OUT #20
+        loop:
+            IN -> [x]
+            EQ [x], #-1 -> [t]
+            JNZ [t], #loop
+            IN -> [y]
+            ADD [addr], #1 -> [dest]
+            EQ [dest], #3 -> [t]
+            JZ [t], #send
+            ADD #255, #0 -> [dest]
+        send:
+            OUT [dest]
+            ADD [x], #1 -> [x]
+            OUT [x]
+            OUT [y]
+            JNZ #1, #loop
+        addr: .data 0
+        x:    .data 0
+        y:    .data 0
+        dest: .data 0
+        t:    .data 0
+        ",
+    )
+    .unwrap();
+
+    let mut network = Network::new(tape.clone(), 3).with_nat();
+
+    let packet = |src, dest, x| Packet {
+        src,
+        dest,
+        x,
+        y: 20,
+    };
+
+    assert_eq!(network.round(), Ok(vec![packet(0, 1, 10)]));
+    assert_eq!(
+        network.run_until(|x| x.dest == 255),
+        Ok(Some(packet(2, 255, 12)))
+    );
+    assert_eq!(network.nat_packet(), Some(packet(2, 255, 12)));
+    assert_eq!(
+        network.run_until(|x| x.src == NAT),
+        Ok(Some(packet(NAT, 0, 12)))
+    );
+    assert!(network.is_idle());
+    assert_eq!(
+        network.run_until(|x| x.src == NAT),
+        Ok(Some(packet(NAT, 0, 15)))
+    );
+
+    let mut network = Network::new(tape, 3);
+    assert_eq!(network.run_until(|x| x.src == NAT), Ok(None));
+    assert_eq!(network.nat_packet(), None);
+
+    let mut network = Network::new(vec![3, 5, 1, -1, 0, 0], 2);
+    let error = network.round().unwrap_err();
+    assert_eq!(error.machine, 0);
+    assert_eq!(error.error.kind, crate::ErrorKind::NegativeAddress(-1));
+    assert_eq!(
+        error.to_string(),
+        "machine 0: negative address -1 at ip 2 (intcode 1, parameter 0)"
+    );
+}
@@ -0,0 +1,282 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Add,
+    Mul,
+    Write,
+    Read,
+    JumpNotZero,
+    JumpZero,
+    LessThan,
+    Equals,
+    AdjustRbp,
+    Halt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ModeError {
+    Illegal(i64),
+    ImmediateDestination,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Op {
+    pub opcode: Opcode,
+    operands: [Operand; 3],
+}
+
+pub(crate) const MODE_IMMEDIATE: u8 = 1;
+pub(crate) const MODE_RELATIVE: u8 = 2;
+
+pub const OPCODES: [Opcode; 10] = [
+    Opcode::Add,
+    Opcode::Mul,
+    Opcode::Write,
+    Opcode::Read,
+    Opcode::JumpNotZero,
+    Opcode::JumpZero,
+    Opcode::LessThan,
+    Opcode::Equals,
+    Opcode::AdjustRbp,
+    Opcode::Halt,
+];
+
+impl Opcode {
+    pub fn from_i64(opcode: i64) -> Option<Opcode> {
+        match opcode {
+            1 => Some(Opcode::Add),
+            2 => Some(Opcode::Mul),
+            3 => Some(Opcode::Write),
+            4 => Some(Opcode::Read),
+            5 => Some(Opcode::JumpNotZero),
+            6 => Some(Opcode::JumpZero),
+            7 => Some(Opcode::LessThan),
+            8 => Some(Opcode::Equals),
+            9 => Some(Opcode::AdjustRbp),
+            99 => Some(Opcode::Halt),
+            _ => None,
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Opcode> {
+        OPCODES
+            .iter()
+            .copied()
+            .find(|x| x.mnemonic().eq_ignore_ascii_case(mnemonic))
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Opcode::Add => 1,
+            Opcode::Mul => 2,
+            Opcode::Write => 3,
+            Opcode::Read => 4,
+            Opcode::JumpNotZero => 5,
+            Opcode::JumpZero => 6,
+            Opcode::LessThan => 7,
+            Opcode::Equals => 8,
+            Opcode::AdjustRbp => 9,
+            Opcode::Halt => 99,
+        }
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "ADD",
+            Opcode::Mul => "MUL",
+            Opcode::Write => "IN",
+            Opcode::Read => "OUT",
+            Opcode::JumpNotZero => "JNZ",
+            Opcode::JumpZero => "JZ",
+            Opcode::LessThan => "LT",
+            Opcode::Equals => "EQ",
+            Opcode::AdjustRbp => "ARB",
+            Opcode::Halt => "HLT",
+        }
+    }
+
+    pub fn arity(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpNotZero | Opcode::JumpZero => 2,
+            Opcode::Write | Opcode::Read | Opcode::AdjustRbp => 1,
+            Opcode::Halt => 0,
+        }
+    }
+
+    pub fn has_dst(self) -> bool {
+        matches!(
+            self,
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals | Opcode::Write
+        )
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())
+    }
+}
+
+impl Operand {
+    pub fn from_mode(mode: i64, value: i64) -> Option<Operand> {
+        match mode {
+            0 => Some(Operand::Position(value)),
+            1 => Some(Operand::Immediate(value)),
+            2 => Some(Operand::Relative(value)),
+            _ => None,
+        }
+    }
+
+    pub fn mode(self) -> i64 {
+        match self {
+            Operand::Position(_) => 0,
+            Operand::Immediate(_) => 1,
+            Operand::Relative(_) => 2,
+        }
+    }
+
+    pub fn value(self) -> i64 {
+        match self {
+            Operand::Position(x) | Operand::Immediate(x) | Operand::Relative(x) => x,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Position(x) => write!(f, "[{}]", x),
+            Operand::Immediate(x) => write!(f, "#{}", x),
+            Operand::Relative(x) if *x < 0 => write!(f, "[rb{}]", x),
+            Operand::Relative(x) => write!(f, "[rb+{}]", x),
+        }
+    }
+}
+
+impl Op {
+    pub fn new(opcode: Opcode, operands: &[Operand]) -> Op {
+        assert_eq!(
+            operands.len(),
+            opcode.arity(),
+            "wrong number of operands for {}",
+            opcode
+        );
+
+        let mut op = Op {
+            opcode,
+            operands: [Operand::Position(0); 3],
+        };
+
+        op.operands[..operands.len()].copy_from_slice(operands);
+
+        op
+    }
+
+    pub fn operands(&self) -> &[Operand] {
+        &self.operands[..self.opcode.arity()]
+    }
+
+    pub fn size(&self) -> usize {
+        self.opcode.arity() + 1
+    }
+
+    pub fn encode(&self) -> Vec<i64> {
+        let intcode = self
+            .operands()
+            .iter()
+            .enumerate()
+            .fold(self.opcode.value(), |acc, (i, x)| {
+                acc + x.mode() * 100 * 10i64.pow(i as u32)
+            });
+
+        let mut words = vec![intcode];
+        words.extend(self.operands().iter().map(|x| x.value()));
+
+        words
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.opcode)?;
+
+        let (srcs, dst) = match self.operands().split_last() {
+            Some((dst, srcs)) if self.opcode.has_dst() => (srcs, Some(dst)),
+            _ => (self.operands(), None),
+        };
+
+        for (i, src) in srcs.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, src)?;
+        }
+
+        if let Some(dst) = dst {
+            write!(f, " -> {}", dst)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn parameter_mode(
+    intcode: i64,
+    opcode: Opcode,
+    parameter: usize,
+) -> Result<u8, ModeError> {
+    match intcode / (100 * 10i64.pow(parameter as u32)) % 10 {
+        1 if opcode.has_dst() && parameter + 1 == opcode.arity() => {
+            Err(ModeError::ImmediateDestination)
+        }
+        x @ 0..=2 => Ok(x as u8),
+        x => Err(ModeError::Illegal(x)),
+    }
+}
+
+pub fn decode(memory: &[i64], addr: usize) -> Option<Op> {
+    let intcode = *memory.get(addr)?;
+
+    if intcode < 0 {
+        return None;
+    }
+
+    let opcode = Opcode::from_i64(intcode % 100)?;
+
+    let mut operands = [Operand::Position(0); 3];
+
+    for (i, operand) in operands.iter_mut().enumerate().take(opcode.arity()) {
+        let mode = parameter_mode(intcode, opcode, i).ok()?;
+
+        *operand = Operand::from_mode(mode as i64, *memory.get(addr + i + 1)?)?;
+    }
+
+    Some(Op { opcode, operands })
+}
+
+#[test]
+fn test_decode_encode() {
+    let op = decode(&[21101, 3, -4, 7], 0).unwrap();
+    assert_eq!(op.opcode, Opcode::Add);
+    assert_eq!(
+        op.operands(),
+        &[
+            Operand::Immediate(3),
+            Operand::Immediate(-4),
+            Operand::Relative(7)
+        ]
+    );
+    assert_eq!(op.encode(), vec![21101, 3, -4, 7]);
+    assert_eq!(op.to_string(), "ADD #3, #-4 -> [rb+7]");
+
+    assert_eq!(decode(&[11101, 3, 4, 7], 0), None);
+    assert_eq!(decode(&[304, 3], 0), None);
+    assert_eq!(decode(&[1, 3, 4], 0), None);
+    assert_eq!(decode(&[42], 0), None);
+}
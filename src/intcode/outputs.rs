@@ -0,0 +1,183 @@
+use crate::{Computer, IntcodeInput, IntcodeOutput, State};
+
+pub struct Outputs<'a, I, O> {
+    computer: &'a mut Computer<I, O>,
+    state: Option<State>,
+}
+
+pub struct Pairs<'a, I, O>(Outputs<'a, I, O>);
+
+pub struct Triples<'a, I, O>(Outputs<'a, I, O>);
+
+impl<I, O> Computer<I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    pub fn outputs(&mut self) -> Outputs<'_, I, O> {
+        Outputs {
+            computer: self,
+            state: None,
+        }
+    }
+}
+
+impl<'a, I, O> Outputs<'a, I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    pub fn state(&self) -> Option<State> {
+        self.state
+    }
+
+    pub fn pairs(self) -> Pairs<'a, I, O> {
+        Pairs(self)
+    }
+
+    pub fn triples(self) -> Triples<'a, I, O> {
+        Triples(self)
+    }
+
+    // An incomplete chunk is kept on the computer, where the next iterator
+    // picks it up once the machine has produced the rest.
+    fn chunk<const N: usize>(&mut self) -> Option<[i64; N]> {
+        let mut chunk = [0; N];
+
+        for i in 0..N {
+            match self.next() {
+                Some(x) => chunk[i] = x,
+                None => {
+                    self.computer.partial_chunk.extend(&chunk[..i]);
+
+                    return None;
+                }
+            }
+        }
+
+        Some(chunk)
+    }
+}
+
+impl<I, O> Iterator for Outputs<'_, I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        if let Some(x) = self.computer.partial_chunk.pop_front() {
+            return Some(x);
+        }
+
+        if let Some(x) = self.computer.output.pop() {
+            return Some(x);
+        }
+
+        if self.state.is_some() {
+            return None;
+        }
+
+        match self.computer.compute_until_output() {
+            State::Output(x) => Some(x),
+            state => {
+                self.state = Some(state);
+                None
+            }
+        }
+    }
+}
+
+impl<I, O> Pairs<'_, I, O> {
+    pub fn state(&self) -> Option<State> {
+        self.0.state
+    }
+}
+
+impl<I, O> Triples<'_, I, O> {
+    pub fn state(&self) -> Option<State> {
+        self.0.state
+    }
+}
+
+impl<I, O> Iterator for Pairs<'_, I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<(i64, i64)> {
+        let [a, b] = self.0.chunk()?;
+        Some((a, b))
+    }
+}
+
+impl<I, O> Iterator for Triples<'_, I, O>
+where
+    I: IntcodeInput,
+    O: IntcodeOutput,
+{
+    type Item = (i64, i64, i64);
+
+    fn next(&mut self) -> Option<(i64, i64, i64)> {
+        let [a, b, c] = self.0.chunk()?;
+        Some((a, b, c))
+    }
+}
+
+#[test]
+fn test_outputs() {
+    let tape = vec![
+        104, 1, 104, 2, 104, 3, 3, 19, 104, 4, 104, 5, 104, 6, 3, 19, 4, 19, 99, 0,
+    ];
+
+    let mut computer = Computer::new(tape);
+
+    let mut pairs = computer.outputs().pairs();
+    assert_eq!(pairs.by_ref().collect::<Vec<_>>(), vec![(1, 2)]);
+    assert_eq!(pairs.state(), Some(State::AwaitingInput));
+    assert_eq!(pairs.next(), None);
+    assert_eq!(computer.output().len(), 0);
+
+    computer.push_input(0);
+    assert_eq!(
+        computer.outputs().pairs().collect::<Vec<_>>(),
+        vec![(3, 4), (5, 6)]
+    );
+
+    computer.push_input(7);
+    assert_eq!(computer.outputs().collect::<Vec<i64>>(), vec![7]);
+    assert!(!computer.is_running());
+
+    let mut computer = Computer::new(vec![104, 1, 104, 2, 104, 3, 104, 4, 99]);
+    assert_eq!(
+        computer.outputs().triples().collect::<Vec<_>>(),
+        vec![(1, 2, 3)]
+    );
+    assert_eq!(computer.pop_output(), None);
+    assert_eq!(computer.outputs().collect::<Vec<i64>>(), vec![4]);
+}
+
+#[test]
+fn test_partial_chunk_with_sender() {
+    let tape = vec![104, 1, 104, 2, 104, 3, 3, 11, 104, 4, 99, 0];
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut computer = Computer::with_io(tape.clone(), std::collections::VecDeque::new(), tx);
+
+    assert_eq!(computer.outputs().pairs().collect::<Vec<_>>(), vec![(1, 2)]);
+    assert_eq!(rx.try_recv().ok(), None);
+
+    computer.input_mut().push_back(0);
+    assert_eq!(computer.outputs().pairs().collect::<Vec<_>>(), vec![(3, 4)]);
+    assert_eq!(rx.try_recv().ok(), None);
+
+    let mut computer = Computer::new(tape);
+    computer.record_history(10);
+    assert_eq!(computer.outputs().pairs().collect::<Vec<_>>(), vec![(1, 2)]);
+    assert!(computer.step_back());
+    assert_eq!(computer.ip(), 4);
+    assert_eq!(computer.output().len(), 0);
+}
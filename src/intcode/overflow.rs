@@ -0,0 +1,38 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    #[default]
+    Wrapping,
+    Checked,
+    Saturating,
+}
+
+impl Overflow {
+    pub fn add(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Overflow::Wrapping => Some(a.wrapping_add(b)),
+            Overflow::Checked => a.checked_add(b),
+            Overflow::Saturating => Some(a.saturating_add(b)),
+        }
+    }
+
+    pub fn mul(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Overflow::Wrapping => Some(a.wrapping_mul(b)),
+            Overflow::Checked => a.checked_mul(b),
+            Overflow::Saturating => Some(a.saturating_mul(b)),
+        }
+    }
+}
+
+#[test]
+fn test_overflow() {
+    assert_eq!(Overflow::Wrapping.add(i64::MAX, 1), Some(i64::MIN));
+    assert_eq!(Overflow::Checked.add(i64::MAX, 1), None);
+    assert_eq!(Overflow::Saturating.add(i64::MAX, 1), Some(i64::MAX));
+
+    assert_eq!(Overflow::Wrapping.mul(i64::MIN, -1), Some(i64::MIN));
+    assert_eq!(Overflow::Checked.mul(i64::MIN, -1), None);
+    assert_eq!(Overflow::Saturating.mul(i64::MIN, 2), Some(i64::MIN));
+
+    assert_eq!(Overflow::Checked.mul(3, -4), Some(-12));
+}
@@ -0,0 +1,85 @@
+use std::panic;
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+
+use crate::{Computer, IntcodeError, IntcodeInput, IntcodeOutput, Memory, State};
+
+pub struct MachineHandle {
+    handle: JoinHandle<Result<State, IntcodeError>>,
+}
+
+impl MachineHandle {
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> Result<State, IntcodeError> {
+        self.handle
+            .join()
+            .unwrap_or_else(|e| panic::resume_unwind(e))
+    }
+}
+
+impl<I, O> Computer<I, O>
+where
+    I: IntcodeInput + Send + 'static,
+    O: IntcodeOutput + Send + 'static,
+{
+    pub fn spawn(self) -> MachineHandle {
+        let mut computer = self;
+
+        MachineHandle {
+            handle: thread::spawn(move || computer.try_compute()),
+        }
+    }
+}
+
+pub fn spawn_connected<M: Into<Memory>>(
+    memory: M,
+    input: Receiver<i64>,
+) -> (MachineHandle, Receiver<i64>) {
+    let (sender, receiver) = mpsc::channel();
+
+    (Computer::with_io(memory, input, sender).spawn(), receiver)
+}
+
+#[test]
+fn test_threaded_pipeline() {
+    let tape = vec![3, 11, 1001, 11, 1, 11, 4, 11, 1105, 1, 0, 0];
+
+    let (sender, mut receiver) = mpsc::channel();
+    let mut handles = Vec::new();
+
+    for _ in 0..3 {
+        let (handle, output) = spawn_connected(tape.clone(), receiver);
+        handles.push(handle);
+        receiver = output;
+    }
+
+    for x in 0..5 {
+        sender.send(x * 10).unwrap();
+    }
+    drop(sender);
+
+    assert_eq!(
+        receiver.iter().collect::<Vec<i64>>(),
+        vec![3, 13, 23, 33, 43]
+    );
+
+    for handle in handles {
+        assert_eq!(handle.join(), Ok(State::AwaitingInput));
+    }
+
+    let (sender, input) = mpsc::channel();
+    let (handle, output) = spawn_connected(vec![3, 5, 4, 5, 99, 0], input);
+    sender.send(7).unwrap();
+    assert_eq!(output.recv(), Ok(7));
+    assert_eq!(output.recv(), Err(mpsc::RecvError));
+    assert_eq!(handle.join(), Ok(State::Halted));
+
+    let (sender, input) = mpsc::channel::<i64>();
+    let (handle, output) = spawn_connected(vec![42], input);
+    assert!(output.recv().is_err());
+    assert!(handle.join().is_err());
+    drop(sender);
+}
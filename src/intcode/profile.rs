@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::op::{Opcode, OPCODES};
+use crate::{Instruction, Memory};
+
+#[derive(Clone, Default)]
+pub struct Profiler {
+    steps: u64,
+    ips: HashMap<usize, u64>,
+    opcodes: HashMap<Opcode, u64>,
+    intcodes: HashMap<i64, u64>,
+    jumps: HashMap<usize, (u64, u64)>,
+    loops: HashMap<(usize, usize), u64>,
+}
+
+impl Profiler {
+    pub fn new() -> Profiler {
+        Profiler::default()
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn hits(&self, ip: usize) -> u64 {
+        self.ips.get(&ip).copied().unwrap_or(0)
+    }
+
+    pub fn opcode_hits(&self, opcode: Opcode) -> u64 {
+        self.opcodes.get(&opcode).copied().unwrap_or(0)
+    }
+
+    pub fn intcode_hits(&self, intcode: i64) -> u64 {
+        self.intcodes.get(&intcode).copied().unwrap_or(0)
+    }
+
+    pub fn jumps(&self, ip: usize) -> (u64, u64) {
+        self.jumps.get(&ip).copied().unwrap_or((0, 0))
+    }
+
+    pub fn hottest(&self, count: usize) -> Vec<(usize, u64)> {
+        let mut ips = self
+            .ips
+            .iter()
+            .map(|(ip, hits)| (*ip, *hits))
+            .collect::<Vec<(usize, u64)>>();
+        ips.sort_by_key(|x| (!x.1, x.0));
+        ips.truncate(count);
+
+        ips
+    }
+
+    pub fn loops(&self) -> Vec<(usize, usize, u64)> {
+        let mut loops = self
+            .loops
+            .iter()
+            .map(|((start, end), iterations)| (*start, *end, *iterations))
+            .collect::<Vec<(usize, usize, u64)>>();
+        loops.sort_by_key(|x| (!x.2, x.0, x.1));
+
+        loops
+    }
+
+    pub(crate) fn record(&mut self, ip: usize, intcode: i64, instruction: &Instruction) {
+        self.steps += 1;
+
+        *self.ips.entry(ip).or_insert(0) += 1;
+        *self.intcodes.entry(intcode).or_insert(0) += 1;
+
+        if let Some(opcode) = Opcode::from_i64(intcode % 100) {
+            *self.opcodes.entry(opcode).or_insert(0) += 1;
+        }
+
+        let (taken, dst) = match *instruction {
+            Instruction::JumpNotZero { cond, dst } => (cond != 0, dst),
+            Instruction::JumpZero { cond, dst } => (cond == 0, dst),
+            _ => return,
+        };
+
+        let jumps = self.jumps.entry(ip).or_insert((0, 0));
+
+        if taken {
+            jumps.0 += 1;
+
+            if dst as usize <= ip {
+                *self.loops.entry((dst as usize, ip)).or_insert(0) += 1;
+            }
+        } else {
+            jumps.1 += 1;
+        }
+    }
+
+    pub fn report(&self, memory: &Memory, count: usize) -> String {
+        let mut report = String::new();
+        let percent = |hits: u64| 100.0 * hits as f64 / self.steps.max(1) as f64;
+
+        let _ = writeln!(report, "{} instructions executed", self.steps);
+
+        let _ = writeln!(report, "\nopcodes:");
+
+        for opcode in OPCODES.iter() {
+            let hits = self.opcode_hits(*opcode);
+
+            if hits > 0 {
+                let _ = writeln!(
+                    report,
+                    "  {:<4} {:>12} {:>6.2}%",
+                    opcode.mnemonic(),
+                    hits,
+                    percent(hits)
+                );
+            }
+        }
+
+        let _ = writeln!(report, "\nparameter modes:");
+
+        let mut intcodes = self
+            .intcodes
+            .iter()
+            .map(|(intcode, hits)| (*intcode, *hits))
+            .collect::<Vec<(i64, u64)>>();
+        intcodes.sort_by_key(|x| (!x.1, x.0));
+
+        for (intcode, hits) in intcodes {
+            let _ = writeln!(
+                report,
+                "  {:05} {:>12} {:>6.2}%",
+                intcode,
+                hits,
+                percent(hits)
+            );
+        }
+
+        let _ = writeln!(report, "\nhottest addresses:");
+
+        for (ip, hits) in self.hottest(count) {
+            let _ = write!(
+                report,
+                "  {:>12} {:>6.2}%  {}",
+                hits,
+                percent(hits),
+                memory.disassemble_at(ip).0
+            );
+
+            if let Some((taken, not_taken)) = self.jumps.get(&ip) {
+                let _ = write!(report, "  (taken {}, not taken {})", taken, not_taken);
+            }
+
+            report.push('\n');
+        }
+
+        let _ = writeln!(report, "\nloops:");
+
+        for (start, end, iterations) in self.loops().into_iter().take(count) {
+            let _ = writeln!(
+                report,
+                "  {:04}..={:04} {:>12} iterations",
+                start, end, iterations
+            );
+
+            let mut addr = start;
+
+            while addr <= end {
+                let (line, len) = memory.disassemble_at(addr);
+                let _ = writeln!(report, "    {:>12}  {}", self.hits(addr), line);
+                addr += len;
+            }
+        }
+
+        report
+    }
+}
+
+#[test]
+fn test_profiler() {
+    use crate::{assemble, Computer};
+
+    let tape = assemble(
+        "
+            ADD #3, #0 -> [n]
+        loop:
+            ADD [n], #-1 -> [n]
+            JNZ [n], #loop
+            OUT [n]
+            HLT
+        n:  .data 0
+        ",
+    )
+    .unwrap();
+
+    let mut computer = Computer::new(tape);
+    computer.profile(Profiler::new());
+    computer.compute();
+
+    let profiler = computer.take_profiler().unwrap();
+    assert_eq!(profiler.steps(), 9);
+    assert_eq!(profiler.hits(4), 3);
+    assert_eq!(profiler.opcode_hits(Opcode::Add), 4);
+    assert_eq!(profiler.intcode_hits(1001), 3);
+    assert_eq!(profiler.jumps(8), (2, 1));
+    assert_eq!(profiler.hottest(1), vec![(4, 3)]);
+    assert_eq!(profiler.loops(), vec![(4, 8, 2)]);
+
+    let report = profiler.report(computer.memory(), 5);
+    assert!(report.contains("0008: JNZ [14], #4  (taken 2, not taken 1)"));
+    assert!(report.contains("0004..=0008            2 iterations"));
+}
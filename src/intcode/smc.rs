@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use crate::Memory;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Modification {
+    pub ip: usize,
+    pub addr: usize,
+    pub old: i64,
+    pub new: i64,
+}
+
+#[derive(Clone, Default)]
+pub struct SmcDetector {
+    executed: BTreeSet<usize>,
+    instructions: BTreeMap<usize, usize>,
+    modifications: Vec<Modification>,
+}
+
+impl SmcDetector {
+    pub fn new() -> SmcDetector {
+        SmcDetector::default()
+    }
+
+    pub fn is_executed(&self, addr: usize) -> bool {
+        self.executed.contains(&addr)
+    }
+
+    pub fn modifications(&self) -> &[Modification] {
+        &self.modifications
+    }
+
+    pub fn regions(&self) -> Vec<Range<usize>> {
+        let addrs = self
+            .modifications
+            .iter()
+            .map(|x| x.addr)
+            .collect::<BTreeSet<usize>>();
+
+        let mut regions: Vec<Range<usize>> = Vec::new();
+
+        for addr in addrs {
+            match regions.last_mut() {
+                Some(region) if region.end == addr => region.end += 1,
+                _ => regions.push(addr..addr + 1),
+            }
+        }
+
+        regions
+    }
+
+    pub(crate) fn record(&mut self, ip: usize, size: usize, write: Option<(usize, i64, i64)>) {
+        self.executed.extend(ip..ip + size);
+        self.instructions.insert(ip, size);
+
+        if let Some((addr, old, new)) = write {
+            if self.executed.contains(&addr) {
+                self.modifications.push(Modification { ip, addr, old, new });
+            }
+        }
+    }
+
+    fn instruction_at(&self, addr: usize) -> Option<usize> {
+        self.instructions
+            .range(..=addr)
+            .rev()
+            .find(|(ip, size)| addr < *ip + *size)
+            .map(|(ip, _)| *ip)
+    }
+
+    pub fn report(&self, memory: &Memory) -> String {
+        let mut report = String::new();
+        let regions = self.regions();
+
+        let _ = writeln!(
+            report,
+            "{} write(s) into executed code, {} region(s)",
+            self.modifications.len(),
+            regions.len()
+        );
+
+        for region in regions {
+            let writes = self
+                .modifications
+                .iter()
+                .filter(|x| region.contains(&x.addr))
+                .collect::<Vec<&Modification>>();
+
+            let _ = writeln!(
+                report,
+                "\n{:04}..{:04} ({} write(s)):",
+                region.start,
+                region.end,
+                writes.len()
+            );
+
+            for x in writes {
+                let _ = writeln!(
+                    report,
+                    "  ip {:04} wrote [{}]: {} -> {}",
+                    x.ip, x.addr, x.old, x.new
+                );
+            }
+
+            let instructions = region
+                .clone()
+                .filter_map(|x| self.instruction_at(x))
+                .collect::<BTreeSet<usize>>();
+
+            for ip in instructions {
+                let _ = writeln!(report, "  now: {}", memory.disassemble_at(ip).0);
+            }
+        }
+
+        report
+    }
+}
+
+#[test]
+fn test_smc_detector() {
+    use crate::Computer;
+
+    let mut computer = Computer::new(vec![1, 0, 0, 0, 99]);
+    computer.detect_smc(SmcDetector::new());
+    computer.compute();
+
+    let detector = computer.take_smc_detector().unwrap();
+    assert_eq!(
+        detector.modifications(),
+        &[Modification {
+            ip: 0,
+            addr: 0,
+            old: 1,
+            new: 2
+        }]
+    );
+    assert!(detector.is_executed(4));
+
+    let mut computer = Computer::new(vec![1101, 7, 0, 13, 1101, 1, 1, 1, 1101, 0, 0, 2, 99, 0]);
+    computer.detect_smc(SmcDetector::new());
+    computer.compute();
+
+    let detector = computer.smc_detector().unwrap();
+    assert_eq!(detector.modifications().len(), 2);
+    assert_eq!(detector.regions(), vec![1..3]);
+
+    let report = detector.report(computer.memory());
+    assert!(report.starts_with("2 write(s) into executed code, 1 region(s)"));
+    assert!(report.contains("0001..0003 (2 write(s)):\n  ip 0004 wrote [1]: 7 -> 2\n"));
+    assert!(report.ends_with("  now: 0000: ADD #2, #0 -> [13]\n"));
+}
@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::iter::FromIterator;
+
+use crate::{Computer, Memory, MemoryKind, Overflow};
+
+const MAGIC: &[u8; 4] = b"ICSN";
+const VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    Checksum,
+    Corrupt,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "{}", e),
+            SnapshotError::BadMagic => write!(f, "not an intcode snapshot"),
+            SnapshotError::UnsupportedVersion(x) => write!(f, "unsupported snapshot version {}", x),
+            SnapshotError::Checksum => write!(f, "snapshot checksum mismatch"),
+            SnapshotError::Corrupt => write!(f, "snapshot is corrupt"),
+        }
+    }
+}
+
+impl Error for SnapshotError {}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> SnapshotError {
+        SnapshotError::Io(e)
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (!(crc & 1)).wrapping_add(1))
+        })
+    })
+}
+
+fn put(buf: &mut Vec<u8>, val: i64) {
+    let mut x = ((val << 1) ^ (val >> 63)) as u64;
+
+    while x >= 0x80 {
+        buf.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+
+    buf.push(x as u8);
+}
+
+fn put_words<'a>(buf: &mut Vec<u8>, words: impl ExactSizeIterator<Item = &'a i64>) {
+    put(buf, words.len() as i64);
+
+    for x in words {
+        put(buf, *x);
+    }
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn get(&mut self) -> Result<i64, SnapshotError> {
+        let mut x = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.get_byte()?;
+
+            x |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok((x >> 1) as i64 ^ -((x & 1) as i64));
+            }
+        }
+
+        Err(SnapshotError::Corrupt)
+    }
+
+    fn get_byte(&mut self) -> Result<u8, SnapshotError> {
+        let byte = *self.data.get(self.pos).ok_or(SnapshotError::Corrupt)?;
+        self.pos += 1;
+
+        Ok(byte)
+    }
+
+    fn get_len(&mut self) -> Result<usize, SnapshotError> {
+        match self.get()? {
+            x if x < 0 || x as usize > self.data.len() - self.pos => Err(SnapshotError::Corrupt),
+            x => Ok(x as usize),
+        }
+    }
+
+    fn get_words<C: FromIterator<i64>>(&mut self) -> Result<C, SnapshotError> {
+        let len = self.get_len()?;
+
+        (0..len).map(|_| self.get()).collect()
+    }
+}
+
+impl Computer {
+    pub fn save<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut buf = MAGIC.to_vec();
+        buf.push(VERSION);
+
+        put(&mut buf, self.ip as i64);
+        put(&mut buf, self.rbp);
+        buf.push(self.is_running as u8);
+        buf.push(match self.overflow {
+            Overflow::Wrapping => 0,
+            Overflow::Checked => 1,
+            Overflow::Saturating => 2,
+        });
+        put(&mut buf, self.steps as i64);
+        put(&mut buf, self.input_waits as i64);
+
+        buf.push(match self.memory.kind() {
+            MemoryKind::Dense => 0,
+            MemoryKind::Paged => 1,
+        });
+        put(&mut buf, self.memory.len() as i64);
+
+        let chunks = self.memory.chunks();
+        put(&mut buf, chunks.len() as i64);
+
+        for (addr, words) in chunks {
+            put(&mut buf, addr as i64);
+            put_words(&mut buf, words.iter());
+        }
+
+        put_words(&mut buf, self.input.iter());
+        put_words(&mut buf, self.output.iter());
+
+        let checksum = crc32(&buf);
+        buf.extend_from_slice(&checksum.to_le_bytes());
+
+        writer.write_all(&buf)
+    }
+
+    pub fn load<R: Read>(mut reader: R) -> Result<Computer, SnapshotError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        if data.len() < MAGIC.len() + 5 || &data[..MAGIC.len()] != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let (data, checksum) = data.split_at(data.len() - 4);
+
+        if crc32(data).to_le_bytes() != checksum {
+            return Err(SnapshotError::Checksum);
+        }
+
+        let version = data[MAGIC.len()];
+
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut cursor = Cursor {
+            data,
+            pos: MAGIC.len() + 1,
+        };
+
+        let ip = cursor.get()?;
+        let rbp = cursor.get()?;
+
+        let is_running = match cursor.get_byte()? {
+            0 => false,
+            1 => true,
+            _ => return Err(SnapshotError::Corrupt),
+        };
+
+        let overflow = match cursor.get_byte()? {
+            0 => Overflow::Wrapping,
+            1 => Overflow::Checked,
+            2 => Overflow::Saturating,
+            _ => return Err(SnapshotError::Corrupt),
+        };
+
+        let steps = cursor.get()? as u64;
+        let input_waits = cursor.get()? as u64;
+
+        let kind = match cursor.get_byte()? {
+            0 => MemoryKind::Dense,
+            1 => MemoryKind::Paged,
+            _ => return Err(SnapshotError::Corrupt),
+        };
+
+        let len = cursor.get()?;
+
+        if len < 0 {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        let mut memory = Memory::new(kind, Vec::new());
+
+        for _ in 0..cursor.get_len()? {
+            let addr = cursor.get()?;
+            let words: Vec<i64> = cursor.get_words()?;
+
+            if addr < 0 || addr as usize + words.len() > len as usize {
+                return Err(SnapshotError::Corrupt);
+            }
+
+            // A dense image is saved as one contiguous run, so anything else
+            // would make us allocate memory the snapshot never stored.
+            if kind == MemoryKind::Dense && addr as usize != memory.len() {
+                return Err(SnapshotError::Corrupt);
+            }
+
+            memory.load(addr as usize, &words);
+        }
+
+        if kind == MemoryKind::Dense && len as usize != memory.len() {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        memory.extend_to(len as usize);
+
+        let input: VecDeque<i64> = cursor.get_words()?;
+        let output: VecDeque<i64> = cursor.get_words()?;
+
+        if ip < 0 || cursor.pos != data.len() {
+            return Err(SnapshotError::Corrupt);
+        }
+
+        let mut computer = Computer::with_io(memory, input, output);
+        computer.ip = ip as usize;
+        computer.rbp = rbp;
+        computer.is_running = is_running;
+        computer.overflow = overflow;
+        computer.steps = steps;
+        computer.input_waits = input_waits;
+
+        Ok(computer)
+    }
+}
+
+#[test]
+fn test_save_and_load() {
+    let mut computer = Computer::new(vec![3, 11, 4, 11, 109, -7, 3, 11, 4, 11, 99, 0]);
+    computer.push_input(-42);
+    computer.compute();
+    computer.push_input(1_000_000_000_000);
+
+    let mut snapshot = Vec::new();
+    computer.save(&mut snapshot).unwrap();
+
+    let mut restored = Computer::load(&snapshot[..]).unwrap();
+    assert_eq!(restored.memory().to_vec(), computer.memory().to_vec());
+    assert_eq!(restored.ip(), 6);
+    assert_eq!(restored.rbp(), -7);
+    assert_eq!(restored.pop_output(), Some(-42));
+    assert_eq!(restored.compute(), crate::State::Halted);
+    assert_eq!(restored.pop_output(), Some(1_000_000_000_000));
+    assert!(!restored.is_running());
+
+    let mut halted = Vec::new();
+    restored.save(&mut halted).unwrap();
+    assert!(!Computer::load(&halted[..]).unwrap().is_running());
+
+    let mut corrupted = snapshot.clone();
+    corrupted[8] ^= 1;
+    assert!(matches!(
+        Computer::load(&corrupted[..]),
+        Err(SnapshotError::Checksum)
+    ));
+
+    assert!(matches!(
+        Computer::load(&snapshot[..snapshot.len() - 1]),
+        Err(SnapshotError::Checksum)
+    ));
+
+    assert!(matches!(
+        Computer::load(&b"1,2,3"[..]),
+        Err(SnapshotError::BadMagic)
+    ));
+
+    let mut computer = Computer::new(vec![3, 0, 99]);
+    computer.set_overflow(Overflow::Checked);
+    computer.compute();
+
+    let mut snapshot = Vec::new();
+    computer.save(&mut snapshot).unwrap();
+
+    let restored = Computer::load(&snapshot[..]).unwrap();
+    assert_eq!(restored.overflow(), Overflow::Checked);
+    assert_eq!((restored.steps(), restored.input_waits()), (0, 1));
+}
+
+#[test]
+fn test_load_oversized_dense() {
+    let mut buf = MAGIC.to_vec();
+    buf.extend_from_slice(&[VERSION, 0, 0, 1, 0, 0, 0, 0]);
+    put(&mut buf, 1 << 40);
+    put(&mut buf, 1);
+    put(&mut buf, 0);
+    put_words(&mut buf, [99].iter());
+    put_words(&mut buf, [].iter());
+    put_words(&mut buf, [].iter());
+
+    let checksum = crc32(&buf);
+    buf.extend_from_slice(&checksum.to_le_bytes());
+
+    assert!(matches!(
+        Computer::load(&buf[..]),
+        Err(SnapshotError::Corrupt)
+    ));
+
+    buf[MAGIC.len() + 7] = 1;
+    let len = buf.len() - 4;
+    let checksum = crc32(&buf[..len]);
+    buf[len..].copy_from_slice(&checksum.to_le_bytes());
+
+    let restored = Computer::load(&buf[..]).unwrap();
+    assert_eq!(restored.memory().len(), 1 << 40);
+}
+
+#[test]
+fn test_save_and_load_sparse() {
+    let mut computer = Computer::new(vec![21101, 7, 0, 1_000_000_000_000, 99]);
+    computer.compute();
+
+    let mut snapshot = Vec::new();
+    computer.save(&mut snapshot).unwrap();
+    assert!(snapshot.len() < 10_000);
+
+    let restored = Computer::load(&snapshot[..]).unwrap();
+    assert_eq!(restored.memory().kind(), MemoryKind::Paged);
+    assert_eq!(restored.memory().len(), 1_000_000_000_001);
+    assert_eq!(restored.get(1_000_000_000_000), Some(7));
+
+    let mut computer = Computer::new(Memory::dense(vec![1101, 1, 2, 7, 99]));
+    computer.compute();
+
+    let mut snapshot = Vec::new();
+    computer.save(&mut snapshot).unwrap();
+
+    let restored = Computer::load(&snapshot[..]).unwrap();
+    assert_eq!(restored.memory().kind(), MemoryKind::Dense);
+    assert_eq!(restored.memory().to_vec(), vec![1101, 1, 2, 7, 99, 0, 0, 3]);
+}
@@ -0,0 +1,558 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::ops;
+use std::rc::Rc;
+
+use crate::op::{decode, Opcode, Operand};
+use crate::Overflow;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Const(i64),
+    Symbol(String),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+    LessThan(Rc<Expr>, Rc<Expr>),
+    Equals(Rc<Expr>, Rc<Expr>),
+    Load(Rc<Expr>),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    terms: BTreeMap<Vec<String>, i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    AwaitingInput,
+    StepLimit,
+    IllegalInstruction { ip: usize },
+    NegativeAddress { ip: usize, addr: i64 },
+    Overflow { ip: usize },
+    SymbolicInstruction { ip: usize, word: Expr },
+    SymbolicBranch { ip: usize, cond: Expr },
+    SymbolicTarget { ip: usize, target: Expr },
+    SymbolicWrite { ip: usize, addr: Expr },
+    SymbolicRbp { ip: usize, value: Expr },
+}
+
+#[derive(Clone)]
+pub struct SymbolicComputer {
+    memory: BTreeMap<usize, Expr>,
+    ip: usize,
+    rbp: i64,
+    input: VecDeque<Expr>,
+    output: Vec<Expr>,
+    steps: u64,
+    overflow: Overflow,
+}
+
+impl Expr {
+    pub fn symbol(name: &str) -> Expr {
+        Expr::Symbol(name.to_string())
+    }
+
+    pub fn constant(&self) -> Option<i64> {
+        match self {
+            Expr::Const(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    // Two equal loads may still read different values if memory was
+    // written in between, so only load-free expressions fold on equality.
+    fn has_load(&self) -> bool {
+        match self {
+            Expr::Const(_) | Expr::Symbol(_) => false,
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::LessThan(a, b) | Expr::Equals(a, b) => {
+                a.has_load() || b.has_load()
+            }
+            Expr::Load(_) => true,
+        }
+    }
+
+    pub fn less_than(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a < b) as i64),
+            (a, b) if a == b && !a.has_load() => Expr::Const(0),
+            (a, b) => Expr::LessThan(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn equals(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a == b) as i64),
+            (a, b) if a == b && !a.has_load() => Expr::Const(1),
+            (a, b) => Expr::Equals(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn polynomial(&self) -> Option<Polynomial> {
+        match self {
+            Expr::Const(x) => Some(Polynomial::constant(*x)),
+            Expr::Symbol(x) => Some(Polynomial::symbol(x)),
+            Expr::Add(a, b) => Some(a.polynomial()?.sum(&b.polynomial()?)),
+            Expr::Mul(a, b) => Some(a.polynomial()?.product(&b.polynomial()?)),
+            _ => None,
+        }
+    }
+}
+
+impl ops::Add for Expr {
+    type Output = Expr;
+
+    fn add(self, other: Expr) -> Expr {
+        match (self, other) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_add(b)),
+            (Expr::Const(0), x) | (x, Expr::Const(0)) => x,
+            (a, b) => Expr::Add(Rc::new(a), Rc::new(b)),
+        }
+    }
+}
+
+impl ops::Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, other: Expr) -> Expr {
+        match (self, other) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_mul(b)),
+            (Expr::Const(0), _) | (_, Expr::Const(0)) => Expr::Const(0),
+            (Expr::Const(1), x) | (x, Expr::Const(1)) => x,
+            (a, b) => Expr::Mul(Rc::new(a), Rc::new(b)),
+        }
+    }
+}
+
+impl From<i64> for Expr {
+    fn from(x: i64) -> Expr {
+        Expr::Const(x)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(x) => write!(f, "{}", x),
+            Expr::Symbol(x) => write!(f, "{}", x),
+            Expr::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expr::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Expr::LessThan(a, b) => write!(f, "({} < {})", a, b),
+            Expr::Equals(a, b) => write!(f, "({} == {})", a, b),
+            Expr::Load(a) => write!(f, "[{}]", a),
+        }
+    }
+}
+
+impl Polynomial {
+    pub fn constant(x: i64) -> Polynomial {
+        let mut terms = BTreeMap::new();
+
+        if x != 0 {
+            terms.insert(Vec::new(), x);
+        }
+
+        Polynomial { terms }
+    }
+
+    pub fn symbol(name: &str) -> Polynomial {
+        let mut terms = BTreeMap::new();
+        terms.insert(vec![name.to_string()], 1);
+
+        Polynomial { terms }
+    }
+
+    pub fn sum(&self, other: &Polynomial) -> Polynomial {
+        let mut terms = self.terms.clone();
+
+        for (monomial, coefficient) in other.terms.iter() {
+            let x = terms.entry(monomial.clone()).or_insert(0);
+            *x = x.wrapping_add(*coefficient);
+        }
+
+        terms.retain(|_, x| *x != 0);
+
+        Polynomial { terms }
+    }
+
+    pub fn product(&self, other: &Polynomial) -> Polynomial {
+        let mut result = Polynomial::default();
+
+        for (a, x) in self.terms.iter() {
+            for (b, y) in other.terms.iter() {
+                let mut monomial = a.iter().chain(b.iter()).cloned().collect::<Vec<String>>();
+                monomial.sort();
+
+                let mut terms = BTreeMap::new();
+                terms.insert(monomial, x.wrapping_mul(*y));
+
+                result = result.sum(&Polynomial { terms });
+            }
+        }
+
+        result
+    }
+
+    pub fn coefficient(&self, monomial: &[&str]) -> i64 {
+        let mut monomial = monomial
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+        monomial.sort();
+
+        self.terms.get(&monomial).copied().unwrap_or(0)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.keys().map(|x| x.len()).max().unwrap_or(0)
+    }
+
+    pub fn eval<F>(&self, value: F) -> i64
+    where
+        F: Fn(&str) -> i64,
+    {
+        self.terms
+            .iter()
+            .fold(0i64, |acc, (monomial, coefficient)| {
+                let term = monomial
+                    .iter()
+                    .fold(*coefficient, |acc, x| acc.wrapping_mul(value(x)));
+
+                acc.wrapping_add(term)
+            })
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut terms = self.terms.iter().collect::<Vec<(&Vec<String>, &i64)>>();
+        terms.sort_by_key(|(monomial, _)| std::cmp::Reverse(monomial.len()));
+
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (i, (monomial, coefficient)) in terms.into_iter().enumerate() {
+            let sign = match (i, *coefficient < 0) {
+                (0, true) => "-",
+                (0, false) => "",
+                (_, true) => " - ",
+                (_, false) => " + ",
+            };
+
+            let magnitude = coefficient.unsigned_abs();
+
+            let term = match (magnitude, monomial.is_empty()) {
+                (x, true) => x.to_string(),
+                (1, false) => monomial.join("*"),
+                (x, false) => format!("{}*{}", x, monomial.join("*")),
+            };
+
+            write!(f, "{}{}", sign, term)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SymbolicComputer {
+    pub fn new(memory: &[i64]) -> SymbolicComputer {
+        SymbolicComputer {
+            memory: memory
+                .iter()
+                .enumerate()
+                .filter(|x| *x.1 != 0)
+                .map(|(addr, x)| (addr, Expr::Const(*x)))
+                .collect(),
+            ip: 0,
+            rbp: 0,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            steps: 0,
+            overflow: Overflow::default(),
+        }
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    pub fn symbol(&mut self, addr: usize, name: &str) {
+        self.set(addr, Expr::symbol(name));
+    }
+
+    pub fn set(&mut self, addr: usize, value: Expr) {
+        match value {
+            Expr::Const(0) => self.memory.remove(&addr),
+            value => self.memory.insert(addr, value),
+        };
+    }
+
+    pub fn get(&self, addr: usize) -> Expr {
+        self.memory.get(&addr).cloned().unwrap_or(Expr::Const(0))
+    }
+
+    pub fn push_input(&mut self, value: Expr) {
+        self.input.push_back(value);
+    }
+
+    pub fn outputs(&self) -> &[Expr] {
+        &self.output
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    fn relative(&self, word: Expr) -> Result<Expr, Stop> {
+        match word {
+            Expr::Const(x) => match self.overflow.add(self.rbp, x) {
+                Some(addr) => Ok(Expr::Const(addr)),
+                None => Err(Stop::Overflow { ip: self.ip }),
+            },
+            word => Ok(Expr::Const(self.rbp) + word),
+        }
+    }
+
+    fn source(&self, operand: Operand, word: Expr) -> Result<Expr, Stop> {
+        let addr = match operand {
+            Operand::Immediate(_) => return Ok(word),
+            Operand::Position(_) => word,
+            Operand::Relative(_) => self.relative(word)?,
+        };
+
+        match addr {
+            Expr::Const(x) if x >= 0 => Ok(self.get(x as usize)),
+            Expr::Const(addr) => Err(Stop::NegativeAddress { ip: self.ip, addr }),
+            addr => Ok(Expr::Load(Rc::new(addr))),
+        }
+    }
+
+    fn destination(&self, operand: Operand, word: Expr) -> Result<usize, Stop> {
+        let addr = match operand {
+            Operand::Relative(_) => self.relative(word)?,
+            _ => word,
+        };
+
+        match addr {
+            Expr::Const(x) if x >= 0 => Ok(x as usize),
+            Expr::Const(addr) => Err(Stop::NegativeAddress { ip: self.ip, addr }),
+            addr => Err(Stop::SymbolicWrite { ip: self.ip, addr }),
+        }
+    }
+
+    pub fn run(&mut self, max_steps: u64) -> Stop {
+        loop {
+            if let Err(stop) = self.step(max_steps) {
+                return stop;
+            }
+        }
+    }
+
+    fn step(&mut self, max_steps: u64) -> Result<(), Stop> {
+        let ip = self.ip;
+
+        if self.steps >= max_steps {
+            return Err(Stop::StepLimit);
+        }
+
+        let intcode = match self.get(ip) {
+            Expr::Const(x) => x,
+            word => return Err(Stop::SymbolicInstruction { ip, word }),
+        };
+
+        let words = (1..4)
+            .map(|i| self.get(ip + i).constant().unwrap_or(0))
+            .collect::<Vec<i64>>();
+
+        let mut header = vec![intcode];
+        header.extend(words);
+
+        let op = decode(&header, 0).ok_or(Stop::IllegalInstruction { ip })?;
+
+        let args = op
+            .operands()
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (*x, self.get(ip + i + 1)))
+            .collect::<Vec<(Operand, Expr)>>();
+
+        let src = |i: usize| self.source(args[i].0, args[i].1.clone());
+
+        let next = ip + op.size();
+
+        match op.opcode {
+            Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => {
+                let (a, b) = (src(0)?, src(1)?);
+                let dst = self.destination(args[2].0, args[2].1.clone())?;
+
+                let value = match (op.opcode, a, b) {
+                    (Opcode::Add, Expr::Const(a), Expr::Const(b)) => {
+                        Expr::Const(self.overflow.add(a, b).ok_or(Stop::Overflow { ip })?)
+                    }
+                    (Opcode::Mul, Expr::Const(a), Expr::Const(b)) => {
+                        Expr::Const(self.overflow.mul(a, b).ok_or(Stop::Overflow { ip })?)
+                    }
+                    (Opcode::Add, a, b) => a + b,
+                    (Opcode::Mul, a, b) => a * b,
+                    (Opcode::LessThan, a, b) => Expr::less_than(a, b),
+                    (_, a, b) => Expr::equals(a, b),
+                };
+
+                self.set(dst, value);
+                self.ip = next;
+            }
+            Opcode::Write => {
+                let dst = self.destination(args[0].0, args[0].1.clone())?;
+                let value = self.input.pop_front().ok_or(Stop::AwaitingInput)?;
+
+                self.set(dst, value);
+                self.ip = next;
+            }
+            Opcode::Read => {
+                let value = src(0)?;
+
+                self.output.push(value);
+                self.ip = next;
+            }
+            Opcode::JumpNotZero | Opcode::JumpZero => {
+                let (cond, target) = (src(0)?, src(1)?);
+
+                let taken = match cond {
+                    Expr::Const(x) => (x != 0) == (op.opcode == Opcode::JumpNotZero),
+                    cond => return Err(Stop::SymbolicBranch { ip, cond }),
+                };
+
+                self.ip = match target {
+                    _ if !taken => next,
+                    Expr::Const(x) if x >= 0 => x as usize,
+                    Expr::Const(addr) => return Err(Stop::NegativeAddress { ip, addr }),
+                    target => return Err(Stop::SymbolicTarget { ip, target }),
+                };
+            }
+            Opcode::AdjustRbp => match src(0)? {
+                Expr::Const(x) => {
+                    self.rbp = self
+                        .overflow
+                        .add(self.rbp, x)
+                        .ok_or(Stop::Overflow { ip })?;
+                    self.ip = next;
+                }
+                value => return Err(Stop::SymbolicRbp { ip, value }),
+            },
+            Opcode::Halt => return Err(Stop::Halted),
+        }
+
+        self.steps += 1;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_symbolic_polynomial() {
+    let tape = [1, 0, 0, 3, 1, 1, 1, 3, 2, 3, 17, 0, 1, 0, 18, 0, 99, 2, 1];
+
+    let mut s = SymbolicComputer::new(&tape);
+    s.symbol(1, "noun");
+    s.symbol(2, "verb");
+
+    assert_eq!(s.run(100), Stop::Halted);
+
+    let output = s.get(0).polynomial().unwrap();
+
+    assert_eq!(output.degree(), 1);
+    assert_eq!(output.coefficient(&["noun"]), 4);
+    assert_eq!(output.coefficient(&["verb"]), 0);
+    assert_eq!(output.to_string(), "4*noun + 1");
+
+    let mut c = crate::Computer::new(tape.to_vec());
+    c.set(1, 7);
+    c.set(2, 3);
+    c.compute();
+
+    assert_eq!(
+        output.eval(|x| if x == "noun" { 7 } else { 3 }),
+        c.get(0).unwrap()
+    );
+}
+
+#[test]
+fn test_symbolic_branch() {
+    let tape = [3, 9, 1005, 9, 8, 104, 0, 99, 99, 0];
+
+    let mut s = SymbolicComputer::new(&tape);
+    s.push_input(Expr::symbol("x"));
+
+    assert_eq!(
+        s.run(100),
+        Stop::SymbolicBranch {
+            ip: 2,
+            cond: Expr::symbol("x")
+        }
+    );
+
+    let mut s = SymbolicComputer::new(&tape);
+    s.push_input(Expr::from(0));
+
+    assert_eq!(s.run(100), Stop::Halted);
+    assert_eq!(s.outputs(), &[Expr::from(0)]);
+}
+
+#[test]
+fn test_symbolic_memory() {
+    let mut s = SymbolicComputer::new(&[1, -1, 0, 0, 99]);
+    assert_eq!(s.run(100), Stop::NegativeAddress { ip: 0, addr: -1 });
+
+    let mut s = SymbolicComputer::new(&[1101, 1, 1, 1_000_000_000_000, 99]);
+    assert_eq!(s.run(100), Stop::Halted);
+    assert_eq!(s.get(1_000_000_000_000), Expr::from(2));
+
+    let load = Expr::Load(Rc::new(Expr::symbol("p")));
+    assert_eq!(
+        Expr::equals(Expr::symbol("x"), Expr::symbol("x")),
+        Expr::from(1)
+    );
+    assert_eq!(
+        Expr::equals(load.clone(), load.clone()),
+        Expr::Equals(Rc::new(load.clone()), Rc::new(load.clone()))
+    );
+    assert_eq!(
+        Expr::less_than(load.clone() + Expr::from(1), load.clone() + Expr::from(1)),
+        Expr::LessThan(
+            Rc::new(load.clone() + Expr::from(1)),
+            Rc::new(load + Expr::from(1))
+        )
+    );
+}
+
+#[test]
+fn test_symbolic_overflow() {
+    let tape = [109, i64::MAX, 204, 1, 99];
+
+    let mut s = SymbolicComputer::new(&tape);
+    assert_eq!(
+        s.run(100),
+        Stop::NegativeAddress {
+            ip: 2,
+            addr: i64::MIN
+        }
+    );
+
+    let mut s = SymbolicComputer::new(&tape);
+    s.set_overflow(Overflow::Checked);
+    assert_eq!(s.run(100), Stop::Overflow { ip: 2 });
+
+    let mut s = SymbolicComputer::new(&[1102, i64::MAX, 2, 5, 99, 0]);
+    s.set_overflow(Overflow::Saturating);
+    assert_eq!(s.run(100), Stop::Halted);
+    assert_eq!(s.get(5), Expr::from(i64::MAX));
+
+    let mut s = SymbolicComputer::new(&[109, i64::MAX, 109, 1, 99]);
+    s.set_overflow(Overflow::Checked);
+    assert_eq!(s.run(100), Stop::Overflow { ip: 2 });
+}
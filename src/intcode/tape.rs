@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Computer;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TapeError {
+    pub index: usize,
+    pub word: String,
+}
+
+impl fmt::Display for TapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not a number: {:?} at index {}", self.word, self.index)
+    }
+}
+
+impl Error for TapeError {}
+
+pub fn parse_tape(tape: &str) -> Result<Vec<i64>, TapeError> {
+    tape.trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, word)| {
+            word.trim().parse::<i64>().map_err(|_| TapeError {
+                index,
+                word: word.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+impl Computer {
+    pub fn try_from_tape(tape: &str) -> Result<Computer, TapeError> {
+        Ok(Computer::new(parse_tape(tape)?))
+    }
+}
+
+#[test]
+fn test_parse_tape() {
+    assert_eq!(parse_tape("1, 2,-3\n"), Ok(vec![1, 2, -3]));
+    assert_eq!(
+        parse_tape("1,2,x3,4"),
+        Err(TapeError {
+            index: 2,
+            word: "x3".to_string()
+        })
+    );
+    assert_eq!(
+        Computer::try_from_tape("1,,2").err().unwrap().to_string(),
+        "not a number: \"\" at index 1"
+    );
+}
@@ -0,0 +1,131 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::ops::Range;
+
+use crate::op::{parameter_mode, Opcode};
+
+pub struct Tracer {
+    sink: Box<dyn Write + Send>,
+    ip_range: Option<Range<usize>>,
+    opcodes: Option<Vec<Opcode>>,
+    steps: u64,
+    error: Option<io::Error>,
+}
+
+pub(crate) struct Record {
+    pub ip: usize,
+    pub rbp: i64,
+    pub intcode: i64,
+    pub opcode: Opcode,
+    pub operands: Vec<i64>,
+    pub write: Option<(usize, i64, i64)>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+}
+
+#[derive(Default)]
+pub(crate) struct TraceSlot(pub Option<Tracer>);
+
+impl Clone for TraceSlot {
+    fn clone(&self) -> TraceSlot {
+        TraceSlot(None)
+    }
+}
+
+impl Tracer {
+    pub fn new<W: Write + Send + 'static>(sink: W) -> Tracer {
+        Tracer {
+            sink: Box::new(sink),
+            ip_range: None,
+            opcodes: None,
+            steps: 0,
+            error: None,
+        }
+    }
+
+    pub fn ip_range(mut self, range: Range<usize>) -> Tracer {
+        self.ip_range = Some(range);
+        self
+    }
+
+    pub fn opcodes(mut self, opcodes: &[Opcode]) -> Tracer {
+        self.opcodes = Some(opcodes.to_vec());
+        self
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+
+    pub(crate) fn wants(&self, ip: usize, opcode: Opcode) -> bool {
+        self.error.is_none()
+            && self.ip_range.as_ref().is_none_or(|x| x.contains(&ip))
+            && self.opcodes.as_ref().is_none_or(|x| x.contains(&opcode))
+    }
+
+    pub(crate) fn tick(&mut self) {
+        self.steps += 1;
+    }
+
+    pub(crate) fn record(&mut self, record: &Record) {
+        let mut line = format!(
+            "{{\"step\":{},\"ip\":{},\"rbp\":{},\"intcode\":{},\"opcode\":{},\"mnemonic\":\"{}\"",
+            self.steps,
+            record.ip,
+            record.rbp,
+            record.intcode,
+            record.opcode.value(),
+            record.opcode.mnemonic()
+        );
+
+        let modes = (0..record.operands.len())
+            .filter_map(|i| parameter_mode(record.intcode, record.opcode, i).ok())
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+
+        let operands = record
+            .operands
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>();
+
+        let _ = write!(
+            line,
+            ",\"modes\":[{}],\"operands\":[{}],\"writes\":[",
+            modes.join(","),
+            operands.join(",")
+        );
+
+        if let Some((addr, old, new)) = record.write {
+            let _ = write!(
+                line,
+                "{{\"addr\":{},\"old\":{},\"new\":{}}}",
+                addr, old, new
+            );
+        }
+
+        line.push(']');
+
+        if let Some(x) = record.input {
+            let _ = write!(line, ",\"input\":{}", x);
+        }
+
+        if let Some(x) = record.output {
+            let _ = write!(line, ",\"output\":{}", x);
+        }
+
+        line.push_str("}\n");
+
+        if let Err(e) = self.sink.write_all(line.as_bytes()) {
+            self.error = Some(e);
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt::Write as _;
+
+use crate::cfg::Cfg;
+use crate::op::{Op, Opcode, Operand};
+
+// Matches MAX_ADDRESS in the generated machine.
+const MAX_ADDRESS: usize = 1 << 20;
+
+const HEADER: &str = "\
+// Generated by intcode-aot. Do not edit.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::mem;
+
+use intcode::{Computer, Overflow, State};
+";
+
+const MACHINE: &str = "
+// Writes at or above this address are left to the interpreter's paged memory.
+const MAX_ADDRESS: usize = 1 << 20;
+
+#[derive(Clone)]
+pub struct Machine {
+    memory: Vec<i64>,
+    ip: usize,
+    rbp: i64,
+    halted: bool,
+    input: VecDeque<i64>,
+    output: VecDeque<i64>,
+    overflow: Overflow,
+    fallback: Option<Computer>,
+}
+
+impl Default for Machine {
+    fn default() -> Machine {
+        Machine::new()
+    }
+}
+
+impl Machine {
+    pub fn new() -> Machine {
+        Machine {
+            memory: TAPE.to_vec(),
+            ip: 0,
+            rbp: 0,
+            halted: false,
+            input: VecDeque::new(),
+            output: VecDeque::new(),
+            overflow: Overflow::default(),
+            fallback: None,
+        }
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.push_input(value),
+            None => self.input.push_back(value),
+        }
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.pop_output(),
+            None => self.output.pop_front(),
+        }
+    }
+
+    pub fn get(&self, addr: usize) -> Option<i64> {
+        match self.fallback.as_ref() {
+            Some(computer) => computer.get(addr),
+            None => self.memory.get(addr).copied(),
+        }
+    }
+
+    pub fn set(&mut self, addr: usize, value: i64) {
+        match self.fallback.as_mut() {
+            Some(computer) => computer.set(addr, value),
+            None if addr >= MAX_ADDRESS => {
+                self.fall_back(self.ip);
+                self.set(addr, value);
+            }
+            None => {
+                if self.store(addr, value) {
+                    self.fall_back(self.ip);
+                }
+            }
+        }
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+
+        if let Some(computer) = self.fallback.as_mut() {
+            computer.set_overflow(overflow);
+        }
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        match self.fallback.as_ref() {
+            Some(computer) => computer.overflow(),
+            None => self.overflow,
+        }
+    }
+
+    pub fn is_interpreted(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    pub fn compute(&mut self) -> State {
+        if self.fallback.is_none() {
+            match self.run() {
+                Ok(state) => return state,
+                Err(ip) => self.fall_back(ip),
+            }
+        }
+
+        self.fallback.as_mut().unwrap().compute()
+    }
+
+    fn fall_back(&mut self, ip: usize) {
+        let mut computer = Computer::with_io(
+            mem::take(&mut self.memory),
+            mem::take(&mut self.input),
+            mem::take(&mut self.output),
+        );
+
+        computer.set_ip(ip);
+        computer.set_rbp(self.rbp);
+        computer.set_overflow(self.overflow);
+
+        self.fallback = Some(computer);
+    }
+
+    fn peek(&self, addr: usize) -> i64 {
+        self.memory.get(addr).copied().unwrap_or(0)
+    }
+
+    fn load(&self, ip: usize, addr: i64) -> Result<i64, usize> {
+        match addr {
+            x if x < 0 => Err(ip),
+            x => Ok(self.peek(x as usize)),
+        }
+    }
+
+    fn add(&self, ip: usize, a: i64, b: i64) -> Result<i64, usize> {
+        self.overflow.add(a, b).ok_or(ip)
+    }
+
+    fn mul(&self, ip: usize, a: i64, b: i64) -> Result<i64, usize> {
+        self.overflow.mul(a, b).ok_or(ip)
+    }
+
+    fn relative(&self, ip: usize, offset: i64) -> Result<i64, usize> {
+        self.add(ip, self.rbp, offset)
+    }
+
+    fn address(ip: usize, addr: i64) -> Result<usize, usize> {
+        match addr {
+            x if x < 0 || x as usize >= MAX_ADDRESS => Err(ip),
+            x => Ok(x as usize),
+        }
+    }
+
+    fn store(&mut self, addr: usize, value: i64) -> bool {
+        if addr >= self.memory.len() {
+            self.memory.resize(addr + 1, 0);
+        }
+
+        self.memory[addr] = value;
+
+        CODE.iter().any(|(start, end)| (*start..*end).contains(&addr))
+    }
+
+    fn run(&mut self) -> Result<State, usize> {
+        if self.halted {
+            return Ok(State::Halted);
+        }
+
+        loop {
+            match self.ip {
+";
+
+const FOOTER: &str = "                ip => return Err(ip),
+            }
+        }
+    }
+}
+";
+
+fn relative(ip: usize, offset: i64) -> String {
+    match offset {
+        0 => "self.rbp".to_string(),
+        x => format!("self.relative({}, {})?", ip, x),
+    }
+}
+
+fn source(ip: usize, operand: Operand) -> String {
+    match operand {
+        Operand::Immediate(x) => x.to_string(),
+        Operand::Position(x) if x >= 0 => format!("self.peek({})", x),
+        Operand::Position(x) => format!("self.load({}, {})?", ip, x),
+        Operand::Relative(x) => format!("self.load({}, {})?", ip, relative(ip, x)),
+    }
+}
+
+fn destination(ip: usize, operand: Operand) -> String {
+    match operand {
+        Operand::Position(x) if x >= 0 && (x as usize) < MAX_ADDRESS => x.to_string(),
+        Operand::Relative(x) => format!("Self::address({}, {})?", ip, relative(ip, x)),
+        operand => format!("Self::address({}, {})?", ip, operand.value()),
+    }
+}
+
+fn target(ip: usize, operand: Operand) -> String {
+    match operand {
+        Operand::Immediate(x) if x >= 0 => x.to_string(),
+        _ => format!("Self::address({}, t)?", ip),
+    }
+}
+
+fn translate(code: &mut String, ip: usize, op: &Op, line: &str) {
+    let next = ip + op.size();
+    let operands = op.operands();
+
+    let mut emit = |line: String| {
+        let _ = writeln!(code, "                    {}", line);
+    };
+
+    emit(format!("// {}", line));
+
+    match op.opcode {
+        Opcode::Add | Opcode::Mul | Opcode::LessThan | Opcode::Equals => {
+            emit(format!("let a = {};", source(ip, operands[0])));
+            emit(format!("let b = {};", source(ip, operands[1])));
+            emit(format!("let d = {};", destination(ip, operands[2])));
+
+            let value = match op.opcode {
+                Opcode::Add => format!("self.add({}, a, b)?", ip),
+                Opcode::Mul => format!("self.mul({}, a, b)?", ip),
+                Opcode::LessThan => "(a < b) as i64".to_string(),
+                _ => "(a == b) as i64".to_string(),
+            };
+
+            emit(format!("if self.store(d, {}) {{", value));
+            emit(format!("    return Err({});", next));
+            emit("}".to_string());
+        }
+        Opcode::Write => {
+            emit(format!("let d = {};", destination(ip, operands[0])));
+            emit("let x = match self.input.pop_front() {".to_string());
+            emit("    Some(x) => x,".to_string());
+            emit("    None => {".to_string());
+            emit(format!("        self.ip = {};", ip));
+            emit("        return Ok(State::AwaitingInput);".to_string());
+            emit("    }".to_string());
+            emit("};".to_string());
+            emit("if self.store(d, x) {".to_string());
+            emit(format!("    return Err({});", next));
+            emit("}".to_string());
+        }
+        Opcode::Read => {
+            emit(format!("let x = {};", source(ip, operands[0])));
+            emit("self.output.push_back(x);".to_string());
+        }
+        Opcode::AdjustRbp => {
+            emit(format!(
+                "self.rbp = self.relative({}, {})?;",
+                ip,
+                source(ip, operands[0])
+            ));
+        }
+        Opcode::JumpNotZero | Opcode::JumpZero => {
+            let (cond, dst) = (operands[0], operands[1]);
+
+            if !matches!(dst, Operand::Immediate(x) if x >= 0) {
+                emit(format!("let t = {};", source(ip, dst)));
+            }
+
+            let test = if op.opcode == Opcode::JumpNotZero {
+                "!="
+            } else {
+                "=="
+            };
+
+            match cond {
+                Operand::Immediate(x) if (x != 0) == (op.opcode == Opcode::JumpNotZero) => {
+                    emit(format!("self.ip = {};", target(ip, dst)));
+                }
+                Operand::Immediate(_) => emit(format!("self.ip = {};", next)),
+                cond => {
+                    emit(format!("self.ip = if {} {} 0 {{", source(ip, cond), test));
+                    emit(format!("    {}", target(ip, dst)));
+                    emit("} else {".to_string());
+                    emit(format!("    {}", next));
+                    emit("};".to_string());
+                }
+            }
+        }
+        Opcode::Halt => {
+            emit(format!("self.ip = {};", ip));
+            emit("self.halted = true;".to_string());
+            emit("return Ok(State::Halted);".to_string());
+        }
+    }
+}
+
+pub fn transpile(memory: &[i64]) -> String {
+    let cfg = Cfg::new(memory);
+
+    let mut code = HEADER.to_string();
+
+    let _ = writeln!(code, "\nconst TAPE: [i64; {}] = [", memory.len());
+
+    for chunk in memory.chunks(8) {
+        let words = chunk.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let _ = writeln!(code, "    {},", words.join(", "));
+    }
+
+    code.push_str("];\n");
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+    for block in cfg.blocks() {
+        match ranges.last_mut() {
+            Some(range) if range.1 >= block.start => range.1 = range.1.max(block.end()),
+            _ => ranges.push((block.start, block.end())),
+        }
+    }
+
+    let _ = writeln!(
+        code,
+        "\nconst CODE: [(usize, usize); {}] = [{}];",
+        ranges.len(),
+        ranges
+            .iter()
+            .map(|(start, end)| format!("({}, {})", start, end))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    code.push_str(MACHINE);
+
+    for block in cfg.blocks() {
+        let _ = writeln!(code, "                {} => {{", block.start);
+
+        for (i, (ip, op)) in block.ops.iter().enumerate() {
+            if i > 0 {
+                code.push('\n');
+            }
+
+            translate(&mut code, *ip, op, cfg.line(*ip).unwrap_or_default());
+        }
+
+        let terminated = block.ops.last().is_some_and(|(_, op)| {
+            matches!(
+                op.opcode,
+                Opcode::JumpNotZero | Opcode::JumpZero | Opcode::Halt
+            )
+        });
+
+        if !terminated {
+            let _ = writeln!(code, "\n                    self.ip = {};", block.end());
+        }
+
+        let _ = writeln!(code, "                }}");
+    }
+
+    code.push_str(FOOTER);
+
+    code
+}
+
+#[test]
+fn test_transpile() {
+    let code = transpile(&[
+        3, 20, 1006, 20, 12, 1001, 20, -1, 20, 1105, 1, 2, 105, 1, 20, 99,
+    ]);
+
+    assert!(code.contains("const CODE: [(usize, usize); 1] = [(0, 15)];"));
+    assert!(code
+        .contains("                    // 0009: JNZ #1, #2\n                    self.ip = 2;\n"));
+    assert!(code.contains("                    let t = self.peek(20);\n                    self.ip = Self::address(12, t)?;\n"));
+    assert!(code.contains(
+        "                    self.ip = if self.peek(20) == 0 {\n                        12\n"
+    ));
+    assert!(!code.contains("15 => {"));
+
+    let code = transpile(&[1101, 1, 2, 1 << 40, 99]);
+    assert!(code.contains("let d = Self::address(0, 1099511627776)?;"));
+
+    let code = transpile(&[204, -3, 99]);
+    assert!(code.contains("let x = self.load(0, self.relative(0, -3)?)?;"));
+
+    let code = transpile(&[1, 0, 0, 0, 109, 7, 99]);
+    assert!(code.contains("if self.store(d, self.add(0, a, b)?) {"));
+    assert!(code.contains("self.rbp = self.relative(4, 7)?;"));
+}
@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+
+use crate::Computer;
+
+#[derive(Clone)]
+pub(crate) struct History {
+    entries: VecDeque<Entry>,
+    capacity: usize,
+}
+
+#[derive(Clone)]
+pub(crate) struct Entry {
+    pub ip: usize,
+    pub rbp: i64,
+    pub len: usize,
+    pub write: Option<(usize, i64)>,
+    pub input: Option<i64>,
+    pub output: Option<i64>,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> History {
+        History {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, entry: Entry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        if self.capacity > 0 {
+            self.entries.push_back(entry);
+        }
+    }
+
+    pub fn note_output(&mut self, value: i64) {
+        if let Some(entry) = self.entries.back_mut() {
+            entry.output = Some(value);
+        }
+    }
+
+    fn contains(&self, f: impl Fn(&Entry) -> bool) -> bool {
+        self.entries.iter().any(f)
+    }
+}
+
+impl Computer {
+    pub fn record_history(&mut self, capacity: usize) {
+        self.history = Some(History::new(capacity));
+    }
+
+    pub fn stop_history(&mut self) {
+        self.history = None;
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.history.as_ref().map_or(0, |x| x.entries.len())
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        let entry = match self.history.as_mut().and_then(|x| x.entries.pop_back()) {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        if let Some((addr, old)) = entry.write {
+            self.store(addr, old);
+            self.memory.truncate(entry.len);
+        }
+
+        if let Some(x) = entry.input {
+            self.input.push_front(x);
+        }
+
+        // Outputs are consumed from the front, so an output that is still
+        // queued is the newest one; if the host took it, there is nothing to undo.
+        if entry.output.is_some_and(|x| self.output.back() == Some(&x)) {
+            self.output.pop_back();
+        }
+
+        self.ip = entry.ip;
+        self.rbp = entry.rbp;
+        self.is_running = true;
+        self.paused = false;
+        self.steps -= 1;
+
+        true
+    }
+
+    pub fn run_back_to(&mut self, ip: usize) -> bool {
+        if !self
+            .history
+            .as_ref()
+            .is_some_and(|x| x.contains(|e| e.ip == ip))
+        {
+            return false;
+        }
+
+        while self.step_back() {
+            if self.ip == ip {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn rewind_to_write(&mut self, addr: usize) -> bool {
+        if !self
+            .history
+            .as_ref()
+            .is_some_and(|x| x.contains(|e| e.write.is_some_and(|x| x.0 == addr)))
+        {
+            return false;
+        }
+
+        loop {
+            let wrote = match self.history.as_ref().and_then(|x| x.entries.back()) {
+                Some(entry) => entry.write.is_some_and(|x| x.0 == addr),
+                None => return false,
+            };
+
+            self.step_back();
+
+            if wrote {
+                return true;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_step_back() {
+    use crate::State;
+
+    let tape = vec![3, 11, 1001, 11, 5, 12, 4, 12, 99, 0, 0, 0, 0];
+
+    let mut computer = Computer::new(tape.clone());
+    computer.record_history(100);
+    computer.push_input(7);
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.output().iter().collect::<Vec<_>>(), vec![&12]);
+
+    assert!(computer.step_back());
+    assert!(computer.is_running());
+    assert_eq!(computer.ip(), 8);
+    assert!(computer.step_back());
+    assert_eq!(computer.pop_output(), None);
+    assert!(computer.run_back_to(0));
+    assert_eq!(computer.memory().to_vec(), tape);
+    assert_eq!(computer.steps(), 0);
+    assert!(!computer.step_back());
+
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(computer.pop_output(), Some(12));
+
+    assert!(computer.rewind_to_write(12));
+    assert_eq!((computer.ip(), computer.get(12)), (2, Some(0)));
+    assert_eq!(computer.history_len(), 1);
+
+    let mut computer = Computer::new(vec![1101, 1, 2, 100, 99]);
+    computer.record_history(1);
+    computer.compute();
+    assert_eq!(computer.memory().len(), 101);
+    assert!(computer.step_back());
+    assert!(!computer.run_back_to(0));
+
+    let mut computer = Computer::new(vec![1101, 1, 2, 100, 99]);
+    computer.record_history(2);
+    computer.compute();
+    assert!(!computer.run_back_to(3));
+    assert!(!computer.rewind_to_write(5));
+    assert_eq!((computer.history_len(), computer.ip()), (2, 4));
+    assert!(computer.run_back_to(0));
+    assert_eq!(computer.memory().to_vec(), vec![1101, 1, 2, 100, 99]);
+}
+
+#[test]
+fn test_step_back_after_pop_output() {
+    let mut computer = Computer::new(vec![104, 1, 104, 2, 99]);
+    computer.record_history(10);
+    computer.compute();
+    assert_eq!(computer.pop_output(), Some(1));
+
+    assert!(computer.step_back());
+    assert!(computer.step_back());
+    assert_eq!(computer.output().len(), 0);
+    assert!(computer.step_back());
+    assert_eq!(computer.output().len(), 0);
+
+    assert_eq!(computer.compute(), crate::State::Halted);
+    assert_eq!(computer.output().iter().collect::<Vec<_>>(), vec![&1, &2]);
+}
@@ -0,0 +1,162 @@
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Any,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchAction {
+    Continue,
+    Pause,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatchEvent {
+    pub ip: usize,
+    pub addr: usize,
+    pub access: Access,
+    pub old: i64,
+    pub new: i64,
+}
+
+type Callback = Box<dyn FnMut(&WatchEvent) -> WatchAction + Send>;
+
+pub(crate) struct Watchpoint {
+    id: usize,
+    addrs: RangeInclusive<usize>,
+    access: Access,
+    callback: Callback,
+}
+
+#[derive(Default)]
+pub(crate) struct WatchSlot {
+    watchpoints: Vec<Watchpoint>,
+    next_id: usize,
+}
+
+impl Clone for WatchSlot {
+    fn clone(&self) -> WatchSlot {
+        WatchSlot::default()
+    }
+}
+
+impl WatchSlot {
+    pub fn is_empty(&self) -> bool {
+        self.watchpoints.is_empty()
+    }
+
+    pub fn add<R, F>(&mut self, addrs: R, access: Access, callback: F) -> usize
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&WatchEvent) -> WatchAction + Send + 'static,
+    {
+        let start = match addrs.start_bound() {
+            Bound::Included(x) => *x,
+            Bound::Excluded(x) => x + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match addrs.end_bound() {
+            Bound::Included(x) => *x,
+            Bound::Excluded(x) => x.checked_sub(1).expect("empty watch range"),
+            Bound::Unbounded => usize::MAX,
+        };
+
+        assert!(start <= end, "empty watch range");
+
+        self.next_id += 1;
+
+        self.watchpoints.push(Watchpoint {
+            id: self.next_id,
+            addrs: start..=end,
+            access,
+            callback: Box::new(callback),
+        });
+
+        self.next_id
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        let len = self.watchpoints.len();
+        self.watchpoints.retain(|x| x.id != id);
+
+        self.watchpoints.len() != len
+    }
+
+    pub fn notify(&mut self, event: &WatchEvent) -> WatchAction {
+        let mut action = WatchAction::Continue;
+
+        for watchpoint in self.watchpoints.iter_mut() {
+            if (watchpoint.access == Access::Any || watchpoint.access == event.access)
+                && watchpoint.addrs.contains(&event.addr)
+                && (watchpoint.callback)(event) == WatchAction::Pause
+            {
+                action = WatchAction::Pause;
+            }
+        }
+
+        action
+    }
+}
+
+#[test]
+fn test_watchpoints() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{Computer, State};
+
+    let tape = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let log = events.clone();
+
+    let mut computer = Computer::new(tape.clone());
+    computer.watch(0..=3, Access::Write, move |x| {
+        log.lock().unwrap().push(*x);
+        match x.addr {
+            0 => WatchAction::Pause,
+            _ => WatchAction::Continue,
+        }
+    });
+
+    assert_eq!(computer.compute(), State::Paused);
+    assert_eq!((computer.ip(), computer.get(0)), (8, Some(3500)));
+    assert_eq!(computer.compute(), State::Halted);
+
+    let write = |ip, addr, old, new| WatchEvent {
+        ip,
+        addr,
+        access: Access::Write,
+        old,
+        new,
+    };
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![write(0, 3, 3, 70), write(4, 0, 1, 3500)]
+    );
+
+    let reads = Arc::new(Mutex::new(Vec::new()));
+    let log = reads.clone();
+
+    let mut computer = Computer::new(tape);
+    let id = computer.watch(9..12, Access::Any, move |x| {
+        log.lock().unwrap().push((x.ip, x.addr, x.access));
+        WatchAction::Continue
+    });
+
+    assert_eq!(computer.compute(), State::Halted);
+    assert_eq!(
+        *reads.lock().unwrap(),
+        vec![
+            (0, 9, Access::Read),
+            (0, 10, Access::Read),
+            (4, 11, Access::Read)
+        ]
+    );
+    assert!(computer.unwatch(id));
+    assert!(!computer.unwatch(id));
+}